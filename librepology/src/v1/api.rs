@@ -2,6 +2,8 @@ use failure::Fallible as Result;
 
 use crate::v1::types::Problem;
 use crate::v1::types::Package;
+use crate::v1::project::Project;
+use crate::v1::filter::ProjectFilter;
 
 /// The high-level functionality of the repology API is represented in this trait
 ///
@@ -17,6 +19,79 @@ pub trait Api {
     fn problems_for_repo<R: AsRef<str>>(&self, repo: R) -> Result<Vec<Problem>>;
 
     fn problems_for_maintainer<M: AsRef<str>>(&self, maintainer: M) -> Result<Vec<Problem>>;
+
+    /// Get one page of projects matching the filter, sorted by name
+    ///
+    /// The page starts at the project named `start` (inclusive), or at the first project if
+    /// `start` is `None`.
+    fn projects(&self, start: Option<&str>, filter: &ProjectFilter) -> Result<Vec<Project>>;
+
+    /// Get all projects matching the filter, page by page, sorted by name
+    ///
+    /// The pages are fetched via `Api::projects()` while iterating, so each page can be used as
+    /// soon as it arrives. Iteration ends after the first error.
+    fn project_pages(&self, filter: ProjectFilter) -> ProjectPages<'_, Self>
+        where Self: Sized
+    {
+        ProjectPages { api: self, filter, start: None, done: false }
+    }
+
+    /// Get all projects matching the filter, sorted by name
+    ///
+    /// This fetches page after page via `Api::project_pages()` until no more projects are returned.
+    fn all_projects(&self, filter: &ProjectFilter) -> Result<Vec<Project>>
+        where Self: Sized
+    {
+        self.project_pages(filter.clone())
+            .collect::<Result<Vec<_>>>()
+            .map(|pages| pages.into_iter().flatten().collect())
+    }
+}
+
+/// The pages of projects matching a filter, see `Api::project_pages()`
+pub struct ProjectPages<'a, A: Api> {
+    api: &'a A,
+    filter: ProjectFilter,
+    start: Option<String>,
+    done: bool,
+}
+
+impl<'a, A: Api> Iterator for ProjectPages<'a, A> {
+    type Item = Result<Vec<Project>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None
+        }
+
+        let start = self.start.take();
+        trace!("Fetching page of projects starting at {:?}", start);
+
+        let page = match self.api.projects(start.as_deref(), &self.filter) {
+            Ok(page) => page,
+            Err(e)   => {
+                self.done = true;
+                return Some(Err(e))
+            },
+        };
+
+        // The page starts with the last project of the previous page, which we already have
+        let page = page
+            .into_iter()
+            .filter(|p| start.as_ref().map(|s| p.name() > s).unwrap_or(true))
+            .collect::<Vec<_>>();
+
+        match page.last() {
+            Some(last) => {
+                self.start = Some(last.name().clone());
+                Some(Ok(page))
+            },
+            None => {
+                self.done = true;
+                None
+            },
+        }
+    }
 }
 
 
@@ -0,0 +1,137 @@
+use url::form_urlencoded;
+
+/// Filter for the projects endpoint of the repology API
+///
+/// Each setter corresponds to one of the filters documented on <https://repology.org/api>.
+/// Filters are combined, so only projects matching all of them are returned.
+#[derive(Eq, PartialEq, Clone, Debug, Default)]
+pub struct ProjectFilter {
+    search: Option<String>,
+    maintainer: Option<String>,
+    category: Option<String>,
+    inrepo: Option<String>,
+    notinrepo: Option<String>,
+    repos: Option<String>,
+    families: Option<String>,
+    repos_newest: Option<String>,
+    families_newest: Option<String>,
+    newest: bool,
+    outdated: bool,
+    problematic: bool,
+}
+
+impl ProjectFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Project name substring to look for
+    pub fn search<S: Into<String>>(mut self, search: S) -> Self {
+        self.search = Some(search.into());
+        self
+    }
+
+    /// Return projects maintained by specified person
+    pub fn maintainer<S: Into<String>>(mut self, maintainer: S) -> Self {
+        self.maintainer = Some(maintainer.into());
+        self
+    }
+
+    /// Return projects with specified category
+    pub fn category<S: Into<String>>(mut self, category: S) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Return projects present in specified repository
+    pub fn inrepo<S: Into<String>>(mut self, repo: S) -> Self {
+        self.inrepo = Some(repo.into());
+        self
+    }
+
+    /// Return projects absent in specified repository
+    pub fn notinrepo<S: Into<String>>(mut self, repo: S) -> Self {
+        self.notinrepo = Some(repo.into());
+        self
+    }
+
+    /// Return projects present in specified number of repositories (exact values and open/closed
+    /// ranges are allowed, e.g. `1`, `5-`, `-5`, `2-7`)
+    pub fn repos<S: Into<String>>(mut self, range: S) -> Self {
+        self.repos = Some(range.into());
+        self
+    }
+
+    /// Same as `repos`, but for repository families
+    pub fn families<S: Into<String>>(mut self, range: S) -> Self {
+        self.families = Some(range.into());
+        self
+    }
+
+    /// Same as `repos`, but only counts repositories with the newest version
+    pub fn repos_newest<S: Into<String>>(mut self, range: S) -> Self {
+        self.repos_newest = Some(range.into());
+        self
+    }
+
+    /// Same as `families`, but only counts repository families with the newest version
+    pub fn families_newest<S: Into<String>>(mut self, range: S) -> Self {
+        self.families_newest = Some(range.into());
+        self
+    }
+
+    /// Return newest projects only (in the repository given with `inrepo`, if any)
+    pub fn newest(mut self, newest: bool) -> Self {
+        self.newest = newest;
+        self
+    }
+
+    /// Return outdated projects only (in the repository given with `inrepo`, if any)
+    pub fn outdated(mut self, outdated: bool) -> Self {
+        self.outdated = outdated;
+        self
+    }
+
+    /// Return problematic projects only (in the repository given with `inrepo`, if any)
+    pub fn problematic(mut self, problematic: bool) -> Self {
+        self.problematic = problematic;
+        self
+    }
+
+    /// Render the filter as query string for the API, without leading "?"
+    pub fn to_query_string(&self) -> String {
+        let mut serializer = form_urlencoded::Serializer::new(String::new());
+
+        {
+            let mut append = |key: &str, value: &Option<String>| {
+                if let Some(value) = value {
+                    serializer.append_pair(key, value);
+                }
+            };
+
+            append("search", &self.search);
+            append("maintainer", &self.maintainer);
+            append("category", &self.category);
+            append("inrepo", &self.inrepo);
+            append("notinrepo", &self.notinrepo);
+            append("repos", &self.repos);
+            append("families", &self.families);
+            append("repos_newest", &self.repos_newest);
+            append("families_newest", &self.families_newest);
+        }
+
+        if self.newest {
+            serializer.append_pair("newest", "1");
+        }
+
+        if self.outdated {
+            serializer.append_pair("outdated", "1");
+        }
+
+        if self.problematic {
+            serializer.append_pair("problematic", "1");
+        }
+
+        serializer.finish()
+    }
+}
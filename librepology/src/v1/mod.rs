@@ -2,3 +2,6 @@ pub mod restapi;
 pub mod stdinapi;
 pub mod api;
 pub mod types;
+pub mod project;
+pub mod vercmp;
+pub mod filter;
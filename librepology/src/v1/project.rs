@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use crate::v1::types::*;
+
+/// A project as known to repology, aggregated from the list of packages returned for it
+///
+/// Repology itself only returns a flat list of packages for a project. This type wraps that list
+/// and provides the statistics one would otherwise have to compute by hand.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Project {
+    name: String,
+    packages: Vec<Package>,
+    found: bool,
+}
+
+impl Project {
+    pub fn new(name: String, packages: Vec<Package>) -> Self {
+        Project { name, packages, found: true }
+    }
+
+    /// A project repology does not know about
+    pub fn not_found(name: String) -> Self {
+        Project { name, packages: vec![], found: false }
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn packages(&self) -> &Vec<Package> {
+        &self.packages
+    }
+
+    pub fn into_packages(self) -> Vec<Package> {
+        self.packages
+    }
+
+    /// Whether repology knows about this project at all
+    ///
+    /// A project which was found but whose packages were all filtered out is still found.
+    pub fn is_found(&self) -> bool {
+        self.found
+    }
+
+    /// Whether there are no packages (left) for this project
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+
+    /// The newest stable version, as marked by repology with `Status::Newest`
+    pub fn newest_version(&self) -> Option<&Version> {
+        self.version_with_status(&Status::Newest)
+    }
+
+    /// The newest stable version, or the version of a project which is only packaged in one
+    /// repository (`Status::Unique`)
+    pub fn newest_or_unique_version(&self) -> Option<&Version> {
+        self.newest_version()
+            .or_else(|| self.version_with_status(&Status::Unique))
+    }
+
+    /// The newest development version, as marked by repology with `Status::Devel`
+    pub fn newest_devel_version(&self) -> Option<&Version> {
+        self.version_with_status(&Status::Devel)
+    }
+
+    fn version_with_status(&self, status: &Status) -> Option<&Version> {
+        self.packages
+            .iter()
+            .find(|p| p.status() == Some(status))
+            .map(Package::version)
+    }
+
+    /// The number of distinct repositories per package status
+    ///
+    /// A repository which ships several packages with the same status is only counted once.
+    pub fn repo_count_by_status(&self) -> BTreeMap<Status, usize> {
+        self.packages
+            .iter()
+            .filter_map(|p| p.status().map(|s| (s, p.repo())))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .fold(BTreeMap::new(), |mut map, (status, _)| {
+                *map.entry(status.clone()).or_insert(0) += 1;
+                map
+            })
+    }
+
+    /// All distinct maintainers of all packages, sorted
+    pub fn maintainers(&self) -> Vec<&Maintainer> {
+        self.packages
+            .iter()
+            .filter_map(Package::maintainers)
+            .flatten()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// The set of licenses reported by a majority of the packages which report licenses at all
+    ///
+    /// Returns `None` if no package reports a license or if there is no majority.
+    pub fn license_consensus(&self) -> Option<Vec<License>> {
+        let reported = self.packages
+            .iter()
+            .filter_map(Package::licenses)
+            .filter(|l| !l.is_empty())
+            .map(|l| l.iter().cloned().collect::<BTreeSet<License>>())
+            .collect::<Vec<_>>();
+
+        let mut counts : BTreeMap<&BTreeSet<License>, usize> = BTreeMap::new();
+        for licenses in reported.iter() {
+            *counts.entry(licenses).or_insert(0) += 1;
+        }
+
+        counts
+            .into_iter()
+            .max_by_key(|(_, count)| *count)
+            .filter(|(_, count)| count * 2 > reported.len())
+            .map(|(licenses, _)| licenses.iter().cloned().collect())
+    }
+
+    /// All distinct repositories this project is packaged in, sorted
+    pub fn repos(&self) -> Vec<&Repo> {
+        self.packages
+            .iter()
+            .map(Package::repo)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// All packages of this project in a certain repository
+    ///
+    /// A repository may contain more than one package for a project (for example a library
+    /// packaged for several interpreter versions), hence this returns a list.
+    pub fn packages_in_repo(&self, repo: &Repo) -> Vec<&Package> {
+        self.packages
+            .iter()
+            .filter(|p| p.repo() == repo)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::Deref;
+
+    fn project_with(packages: &str) -> Project {
+        Project::new(String::from("foo"), serde_json::from_str(packages).unwrap())
+    }
+
+    fn license_names(licenses: Option<Vec<License>>) -> Option<Vec<String>> {
+        licenses.map(|ls| ls.iter().map(|l| l.deref().clone()).collect())
+    }
+
+    #[test]
+    fn test_license_consensus_majority() {
+        let project = project_with(r#"[
+            {"repo": "arch", "name": "foo", "version": "1.0", "licenses": ["MIT", "GPL-2.0"]},
+            {"repo": "debian_12", "name": "foo", "version": "1.0", "licenses": ["GPL-2.0", "MIT"]},
+            {"repo": "fedora_39", "name": "foo", "version": "1.0", "licenses": ["BSD-3-Clause"]}
+        ]"#);
+
+        assert_eq!(license_names(project.license_consensus()),
+                   Some(vec![String::from("GPL-2.0"), String::from("MIT")]));
+    }
+
+    #[test]
+    fn test_license_consensus_tie() {
+        let project = project_with(r#"[
+            {"repo": "arch", "name": "foo", "version": "1.0", "licenses": ["MIT"]},
+            {"repo": "debian_12", "name": "foo", "version": "1.0", "licenses": ["BSD-3-Clause"]}
+        ]"#);
+
+        assert_eq!(project.license_consensus(), None);
+    }
+
+    #[test]
+    fn test_license_consensus_missing_licenses() {
+        let project = project_with(r#"[
+            {"repo": "arch", "name": "foo", "version": "1.0", "licenses": ["MIT"]},
+            {"repo": "debian_12", "name": "foo", "version": "1.0"},
+            {"repo": "fedora_39", "name": "foo", "version": "1.0", "licenses": []}
+        ]"#);
+
+        // Packages without licenses do not count against the majority
+        assert_eq!(license_names(project.license_consensus()), Some(vec![String::from("MIT")]));
+
+        let project = project_with(r#"[
+            {"repo": "arch", "name": "foo", "version": "1.0"},
+            {"repo": "debian_12", "name": "foo", "version": "1.0", "licenses": []}
+        ]"#);
+        assert_eq!(project.license_consensus(), None);
+    }
+
+    #[test]
+    fn test_repo_count_by_status() {
+        let project = project_with(r#"[
+            {"repo": "arch", "name": "foo", "version": "1.2", "status": "newest"},
+            {"repo": "pypi", "name": "py36-foo", "version": "1.0", "status": "outdated"},
+            {"repo": "pypi", "name": "py37-foo", "version": "1.0", "status": "outdated"},
+            {"repo": "debian_12", "name": "foo", "version": "1.0", "status": "outdated"},
+            {"repo": "gentoo", "name": "foo", "version": "1.2"}
+        ]"#);
+
+        let counts = project.repo_count_by_status();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts.get(&Status::Newest), Some(&1));
+        assert_eq!(counts.get(&Status::Outdated), Some(&2));
+    }
+
+    #[test]
+    fn test_not_found() {
+        let project = Project::not_found(String::from("nope"));
+
+        assert!(!project.is_found());
+        assert!(project.is_empty());
+        assert!(project.repo_count_by_status().is_empty());
+        assert_eq!(project.license_consensus(), None);
+        assert_eq!(project.newest_version(), None);
+        assert!(project.repos().is_empty());
+    }
+
+    #[test]
+    fn test_found_but_empty() {
+        let project = project_with("[]");
+
+        assert!(project.is_found());
+        assert!(project.is_empty());
+        assert!(project.repo_count_by_status().is_empty());
+        assert_eq!(project.license_consensus(), None);
+    }
+}
@@ -1,11 +1,21 @@
 use std::result::Result as RResult;
+use std::cell::Cell;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
 
 use failure::Error;
 use failure::Fallible as Result;
 use curl::easy::Easy2;
+use url::percent_encoding::utf8_percent_encode;
+use url::percent_encoding::PATH_SEGMENT_ENCODE_SET;
+
+use std::collections::BTreeMap;
 
 use crate::v1::types::{Package, Problem};
 use crate::v1::api::Api;
+use crate::v1::project::Project;
+use crate::v1::filter::ProjectFilter;
 
 /// Private helper type for collecting data from the curl library
 struct Collector(Vec<u8>);
@@ -16,28 +26,132 @@ impl curl::easy::Handler for Collector {
     }
 }
 
+/// Minimum time between two requests, as repology asks API users to do not more than one request
+/// per second
+const REQUEST_INTERVAL : Duration = Duration::from_secs(1);
+
 /// Representational object for the REST Api of repology
+///
+/// Responses with an HTTP status other than 200 are returned as errors.
 pub struct RestApi {
     /// Base url
     repology: String,
+
+    /// Time of the last request, for rate limiting
+    last_request: Cell<Option<Instant>>,
+
+    /// Cache for responses, if enabled
+    cache: Option<ResponseCache>,
 }
 
 impl RestApi {
     pub fn new(repology: String) -> Self {
-        Self { repology }
+        Self { repology, last_request: Cell::new(None), cache: None }
     }
 
-    /// Helper function for sending a request via the curl library
+    /// Cache responses in `dir`, and reuse them for `ttl`
+    pub fn with_cache(mut self, dir: PathBuf, ttl: Duration) -> Self {
+        self.cache = Some(ResponseCache { dir, ttl });
+        self
+    }
+
+    /// Helper function for sending a request, answered from the cache if possible
     fn send_request<U: AsRef<str>>(&self, request: U) -> Result<String> {
+        let request = request.as_ref();
+        if let Some(cache) = self.cache.as_ref() {
+            if let Some(response) = cache.get(request) {
+                trace!("Answered from cache: {}", request);
+                return Ok(response)
+            }
+        }
+
+        let response = self.send_request_uncached(request)?;
+
+        if let Some(cache) = self.cache.as_ref() {
+            if let Err(e) = cache.put(request, &response) {
+                warn!("Could not cache response: {}", e);
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Helper function for sending a request via the curl library
+    ///
+    /// Blocks if the previous request was sent less than `REQUEST_INTERVAL` ago. Fails if the
+    /// response has an HTTP status other than 200.
+    fn send_request_uncached(&self, request: &str) -> Result<String> {
+        if let Some(last) = self.last_request.get() {
+            let elapsed = last.elapsed();
+            if elapsed < REQUEST_INTERVAL {
+                trace!("Rate limiting, waiting {:?}", REQUEST_INTERVAL - elapsed);
+                std::thread::sleep(REQUEST_INTERVAL - elapsed);
+            }
+        }
+        self.last_request.set(Some(Instant::now()));
+
         let mut easy = Easy2::new(Collector(Vec::new()));
         easy.get(true)?;
-        easy.url(request.as_ref())?;
+        easy.url(request)?;
         easy.perform()?;
+
+        let code = easy.response_code()?;
+        if code != 200 {
+            return Err(failure::format_err!("Request to {} failed with HTTP status {}", request, code))
+        }
+
         let content = easy.get_ref().0.clone(); // TODO: Ugh...
         String::from_utf8(content).map_err(Error::from)
     }
 }
 
+/// Responses cached on disk, one file per URL
+struct ResponseCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+/// The 64 bit FNV-1a hash of `bytes`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+impl ResponseCache {
+    /// The file of the cached response, named by a digest of the URL which does not change
+    /// between builds
+    fn path(&self, url: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", fnv1a(url.as_bytes())))
+    }
+
+    /// Get the cached response for `url`, if there is one which is not older than the TTL
+    fn get(&self, url: &str) -> Option<String> {
+        let path = self.path(url);
+        let age = std::fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())?;
+
+        if age > self.ttl {
+            trace!("Cached response for {} expired", url);
+            return None
+        }
+
+        std::fs::read_to_string(path).ok()
+    }
+
+    fn put(&self, url: &str, response: &str) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        // Write to a temporary file first, so concurrent readers never see partial responses
+        let path = self.path(url);
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, response)?;
+        std::fs::rename(tmp, path).map_err(Error::from)
+    }
+}
+
 impl Api for RestApi {
 
     fn project<N: AsRef<str>>(&self, name: N) -> Result<Vec<Package>> {
@@ -58,4 +172,40 @@ impl Api for RestApi {
         serde_json::from_str(&self.send_request(url)?).map_err(Error::from)
     }
 
+    fn projects(&self, start: Option<&str>, filter: &ProjectFilter) -> Result<Vec<Project>> {
+        let url = match start {
+            Some(start) => {
+                let start = utf8_percent_encode(start, PATH_SEGMENT_ENCODE_SET);
+                format!("{}api/v1/projects/{}/?{}", self.repology, start, filter.to_query_string())
+            },
+            None        => format!("{}api/v1/projects/?{}", self.repology, filter.to_query_string()),
+        };
+        trace!("Request: {}", url);
+        serde_json::from_str::<BTreeMap<String, Vec<Package>>>(&self.send_request(url)?)
+            .map(|projects| projects.into_iter().map(|(name, packages)| Project::new(name, packages)).collect())
+            .map_err(Error::from)
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn test_cache_path() {
+        let cache = ResponseCache { dir: PathBuf::from("/cache"), ttl: Duration::from_secs(60) };
+        assert_eq!(cache.path("https://repology.org/api/v1/project/foo"),
+                   cache.path("https://repology.org/api/v1/project/foo"));
+        assert_ne!(cache.path("https://repology.org/api/v1/project/foo"),
+                   cache.path("https://repology.org/api/v1/project/bar"));
+        assert_eq!(cache.path("").to_str(), Some("/cache/cbf29ce484222325.json"));
+    }
 }
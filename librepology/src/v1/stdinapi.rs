@@ -6,9 +6,13 @@ use std::ops::DerefMut;
 use failure::Fallible as Result;
 use failure::Error;
 
+use std::collections::BTreeMap;
+
 use crate::v1::types::Problem;
 use crate::v1::types::Package;
 use crate::v1::api::Api;
+use crate::v1::project::Project;
+use crate::v1::filter::ProjectFilter;
 
 /// Wrapper for "stdin"
 ///
@@ -53,6 +57,19 @@ impl Api for StdinWrapper {
         serde_json::de::from_str(&s).map_err(Error::from)
     }
 
+    /// All data on stdin is returned as the first page, so asking for any later page (by passing
+    /// `start`) yields no projects.
+    fn projects(&self, start: Option<&str>, _filter: &ProjectFilter) -> Result<Vec<Project>> {
+        if start.is_some() {
+            return Ok(vec![])
+        }
+
+        let s = read_to_string(self.0.try_borrow_mut()?.deref_mut())?;
+        serde_json::de::from_str::<BTreeMap<String, Vec<Package>>>(&s)
+            .map(|projects| projects.into_iter().map(|(name, packages)| Project::new(name, packages)).collect())
+            .map_err(Error::from)
+    }
+
 }
 
 fn read_to_string(input: &mut dyn Read) -> Result<String> {
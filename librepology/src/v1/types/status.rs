@@ -1,5 +1,7 @@
 // package status
-#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, Display)]
+//
+// The variants are ordered by precedence (newest first), which is what the derived `Ord` uses.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Debug, Serialize, Deserialize, Display)]
 pub enum Status {
     #[serde(rename = "newest")]
     #[display(fmt = "newest")]
@@ -0,0 +1,183 @@
+//! Version comparison the way repology does it
+//!
+//! This is a simplified implementation of the algorithm of
+//! [libversion](https://github.com/repology/libversion), which repology uses to compare package
+//! versions: A version is split into numeric and alphabetic components, alphabetic components are
+//! classified (pre-release keywords, post-release keywords, letter suffixes) and the components
+//! are then compared one by one, padding the shorter version with zeros.
+//!
+//! This means that `1.0 == 1.0.0`, `1.0alpha1 < 1.0 < 1.0patch1 < 1.0.1`, `1.0 < 1.0a < 1.0.1`
+//! and `1.10 > 1.9`.
+
+use std::cmp::Ordering;
+
+/// Keywords which denote a release _after_ the version they are attached to
+const POST_RELEASE_KEYWORDS : &[&str] = &["errata", "patch", "pl", "post"];
+
+/// The order of the kinds of components
+///
+/// A letter suffix is a release after the version it is attached to, but before the next
+/// numeric component: `1.0 < 1.0a < 1.0.1`.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Debug)]
+enum Rank {
+    PreRelease,
+    Zero,
+    PostRelease,
+    LetterSuffix,
+    NonZero,
+}
+
+#[derive(Eq, PartialEq, Debug)]
+struct Component {
+    rank: Rank,
+
+    /// Either the digits (without leading zeros) or the lowercased letters of the component
+    value: String,
+}
+
+impl Component {
+    fn padding() -> Self {
+        Component { rank: Rank::Zero, value: String::new() }
+    }
+
+    fn numeric(digits: &str) -> Self {
+        let value = digits.trim_start_matches('0').to_string();
+        let rank  = if value.is_empty() { Rank::Zero } else { Rank::NonZero };
+        Component { rank, value }
+    }
+
+    fn alphabetic(letters: &str, is_suffix: bool) -> Self {
+        let value = letters.to_lowercase();
+        let rank  = if is_suffix {
+            Rank::LetterSuffix
+        } else if POST_RELEASE_KEYWORDS.contains(&value.as_str()) {
+            Rank::PostRelease
+        } else {
+            Rank::PreRelease
+        };
+        Component { rank, value }
+    }
+}
+
+impl Ord for Component {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank.cmp(&other.rank).then_with(|| match self.rank {
+            // numbers are compared by length first, so we do not overflow on huge ones
+            Rank::Zero | Rank::NonZero => self.value.len()
+                .cmp(&other.value.len())
+                .then_with(|| self.value.cmp(&other.value)),
+            _ => self.value.cmp(&other.value),
+        })
+    }
+}
+
+impl PartialOrd for Component {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn components(version: &str) -> Vec<Component> {
+    let chars = version.chars().collect::<Vec<char>>();
+    let mut result = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i].is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            result.push(Component::numeric(&chars[start..i].iter().collect::<String>()));
+        } else if chars[i].is_alphabetic() {
+            let start = i;
+            while i < chars.len() && chars[i].is_alphabetic() {
+                i += 1;
+            }
+
+            // "1.0a" is a letter suffix, "1.0alpha1" and "1.0 a" are not
+            let follows_number = start > 0 && chars[start - 1].is_ascii_digit();
+            let followed_by_number = i < chars.len() && chars[i].is_ascii_digit();
+            let is_suffix = follows_number && !followed_by_number && i - start == 1;
+
+            result.push(Component::alphabetic(&chars[start..i].iter().collect::<String>(), is_suffix));
+        } else {
+            i += 1;
+        }
+    }
+
+    result
+}
+
+/// Compare two versions
+pub fn compare(a: &str, b: &str) -> Ordering {
+    let mut a = components(a).into_iter();
+    let mut b = components(b).into_iter();
+
+    loop {
+        let ordering = match (a.next(), b.next()) {
+            (None, None)       => return Ordering::Equal,
+            (Some(x), None)    => x.cmp(&Component::padding()),
+            (None, Some(y))    => Component::padding().cmp(&y),
+            (Some(x), Some(y)) => x.cmp(&y),
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compare;
+    use std::cmp::Ordering;
+
+    fn assert_order(lower: &str, higher: &str) {
+        assert_eq!(compare(lower, higher), Ordering::Less, "{} < {}", lower, higher);
+        assert_eq!(compare(higher, lower), Ordering::Greater, "{} > {}", higher, lower);
+    }
+
+    #[test]
+    fn test_equal() {
+        assert_eq!(compare("1.0", "1.0"), Ordering::Equal);
+        assert_eq!(compare("1.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare("1.01", "1.1"), Ordering::Equal);
+        assert_eq!(compare("1.0-1", "1.0.1"), Ordering::Equal);
+        assert_eq!(compare("1.0ALPHA", "1.0alpha"), Ordering::Equal);
+        assert_eq!(compare("", ""), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_numeric() {
+        assert_order("1.9", "1.10");
+        assert_order("1.0", "1.0.1");
+        assert_order("0.9", "1");
+        assert_order("99999999999999999999", "100000000000000000000");
+    }
+
+    #[test]
+    fn test_pre_release() {
+        assert_order("1.0alpha1", "1.0");
+        assert_order("1.0alpha1", "1.0beta1");
+        assert_order("1.0rc1", "1.0");
+        assert_order("1.0alpha1", "1.0alpha2");
+        assert_order("0.9", "1.0alpha1");
+    }
+
+    #[test]
+    fn test_post_release() {
+        assert_order("1.0", "1.0patch1");
+        assert_order("1.0patch1", "1.0.1");
+        assert_order("1.0pl1", "1.0pl2");
+    }
+
+    #[test]
+    fn test_letter_suffix() {
+        assert_order("1.0", "1.0a");
+        assert_order("1.0a", "1.0b");
+        assert_order("1.0a", "1.0.1");
+        assert_order("1.0patch1", "1.0a");
+        assert_order("1.0a", "1.1");
+    }
+}
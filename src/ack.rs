@@ -0,0 +1,159 @@
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::Utc;
+use failure::Error;
+use failure::Fallible as Result;
+use failure::ResultExt;
+
+use librepology::v1::types::Problem;
+
+use crate::problemkind::ProblemKind;
+
+/// An acknowledged problem
+///
+/// The acknowledgement only applies as long as the problem description does not change.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct Acknowledgement {
+    repo: String,
+    name: String,
+    kind: ProblemKind,
+    problem: String,
+    reason: Option<String>,
+    timestamp: DateTime<Utc>,
+}
+
+impl Acknowledgement {
+    pub fn new(problem: &Problem, reason: Option<String>, timestamp: DateTime<Utc>) -> Self {
+        Acknowledgement {
+            repo: problem.repo().to_string(),
+            name: problem.name().to_string(),
+            kind: ProblemKind::of(problem),
+            problem: problem.problem_description().clone(),
+            reason,
+            timestamp,
+        }
+    }
+
+    pub fn repo(&self) -> &String {
+        &self.repo
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn kind(&self) -> ProblemKind {
+        self.kind
+    }
+
+    /// Whether the acknowledgement is about the same package and kind of problem
+    fn is_about(&self, problem: &Problem) -> bool {
+        self.repo == **problem.repo() && self.name == **problem.name() && self.kind == ProblemKind::of(problem)
+    }
+
+    /// Whether the acknowledgement applies to a problem
+    fn applies_to(&self, problem: &Problem) -> bool {
+        self.is_about(problem) && self.problem == *problem.problem_description()
+    }
+
+    fn is_expired(&self, problems: &[Problem]) -> bool {
+        problems.iter().any(|p| self.is_about(p)) && !problems.iter().any(|p| self.applies_to(p))
+    }
+}
+
+/// Whether a problem is the one identified by repository, package name and kind
+pub fn is_problem(problem: &Problem, repo: &str, name: &str, kind: ProblemKind) -> bool {
+    **problem.repo() == repo && **problem.name() == name && ProblemKind::of(problem) == kind
+}
+
+/// Local store for acknowledged problems
+///
+/// The store is a single JSON file with a list of acknowledgements, see the README for the format.
+pub struct AckStore {
+    path: PathBuf,
+    acks: Vec<Acknowledgement>,
+}
+
+impl AckStore {
+    /// Open the store in `$XDG_DATA_HOME/repolocli/acknowledgements.json` for reading
+    ///
+    /// Nothing is created, if there is no store yet it is empty.
+    pub fn load_default() -> Result<Self> {
+        let path = xdg::BaseDirectories::with_prefix("repolocli")?
+            .get_data_home()
+            .join("acknowledgements.json");
+
+        AckStore::load(path)
+    }
+
+    /// Open the store in `$XDG_DATA_HOME/repolocli/acknowledgements.json` for changing it
+    ///
+    /// Creates the data directory, so that the store can be saved.
+    pub fn open_default() -> Result<Self> {
+        let path = xdg::BaseDirectories::with_prefix("repolocli")?
+            .place_data_file("acknowledgements.json")
+            .context("Creating data directory")?;
+
+        AckStore::load(path)
+    }
+
+    fn load(path: PathBuf) -> Result<Self> {
+        let acks = if path.exists() {
+            let buffer = std::fs::read_to_string(&path)?;
+            serde_json::from_str(&buffer)
+                .with_context(|_| format!("Parsing acknowledgements in {}", path.display()))?
+        } else {
+            trace!("No acknowledgements at {}", path.display());
+            vec![]
+        };
+
+        Ok(AckStore { path, acks })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        trace!("Writing acknowledgements to {}", self.path.display());
+        std::fs::write(&self.path, serde_json::to_string_pretty(&self.acks)?).map_err(Error::from)
+    }
+
+    /// Acknowledge a problem, replacing an earlier acknowledgement of it
+    pub fn ack(&mut self, ack: Acknowledgement) {
+        self.acks.retain(|a| !(a.repo == ack.repo && a.name == ack.name && a.kind == ack.kind && a.problem == ack.problem));
+        self.acks.push(ack);
+    }
+
+    /// Remove the acknowledgements for a package and kind of problem
+    ///
+    /// Returns the removed acknowledgements.
+    pub fn unack(&mut self, repo: &str, name: &str, kind: ProblemKind) -> Vec<Acknowledgement> {
+        let (removed, kept) = self.acks
+            .drain(..)
+            .partition(|a| a.repo == repo && a.name == name && a.kind == kind);
+        self.acks = kept;
+        removed
+    }
+
+    pub fn is_acked(&self, problem: &Problem) -> bool {
+        self.acks.iter().any(|a| a.applies_to(problem))
+    }
+
+    /// The acknowledgements of problems whose description changed
+    ///
+    /// An acknowledgement expired if there is a problem of the same package and kind, but none
+    /// with the acknowledged description. Expired acknowledgements do not apply to any problem.
+    pub fn expired(&self, problems: &[Problem]) -> Vec<&Acknowledgement> {
+        self.acks
+            .iter()
+            .filter(|a| a.is_expired(problems))
+            .collect()
+    }
+
+    /// Remove the expired acknowledgements, returns them
+    pub fn expire(&mut self, problems: &[Problem]) -> Vec<Acknowledgement> {
+        let (expired, kept) = self.acks
+            .drain(..)
+            .partition(|a| a.is_expired(problems));
+        self.acks = kept;
+        expired
+    }
+}
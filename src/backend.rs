@@ -2,16 +2,18 @@ use clap::ArgMatches;
 use failure::Fallible as Result;
 
 use librepology::v1::api::Api;
+use librepology::v1::filter::ProjectFilter;
+use librepology::v1::project::Project;
 use librepology::v1::restapi::RestApi;
-use librepology::v1::stdinapi::StdinWrapper;
 use librepology::v1::types::*;
 
 use crate::config::Configuration;
+use crate::input::StdinInput;
 
 /// Helper type for cli implementation
 /// for being transparent in what backend we use
 pub enum Backend {
-    Stdin(StdinWrapper),
+    Stdin(StdinInput),
     RepologyOrg(RestApi),
 }
 
@@ -39,16 +41,38 @@ impl Api for Backend {
             Backend::RepologyOrg(inner) => inner.problems_for_maintainer(maintainer),
         }
     }
+
+    fn projects(&self, start: Option<&str>, filter: &ProjectFilter) -> Result<Vec<Project>> {
+        match self {
+            Backend::Stdin(inner) => inner.projects(start, filter),
+            Backend::RepologyOrg(inner) => inner.projects(start, filter),
+        }
+    }
 }
 
 pub fn new_backend(app: &ArgMatches, config: &Configuration) -> Result<Backend> {
     if app.is_present("input_stdin") {
         trace!("Building new STDIN backend");
-        Ok(Backend::Stdin(StdinWrapper::from(::std::io::stdin())))
+        Ok(Backend::Stdin(StdinInput::default()))
     } else {
         trace!("Building new remote backend");
         let url = config.repology_url().as_str().into();
         trace!("url = {}", url);
-        Ok(Backend::RepologyOrg(RestApi::new(url)))
+        let api = RestApi::new(url);
+
+        // Only "watch" polls the same URLs over and over, everything else gets fresh responses
+        let ttl = match app.subcommand_name() {
+            Some("watch") => config.cache().ttl()?,
+            _             => None,
+        };
+
+        match ttl {
+            Some(ttl) => {
+                let dir = xdg::BaseDirectories::with_prefix("repolocli")?.create_cache_directory("responses")?;
+                trace!("Caching responses in {} for {:?}", dir.display(), ttl);
+                Ok(Backend::RepologyOrg(api.with_cache(dir, ttl)))
+            },
+            None => Ok(Backend::RepologyOrg(api)),
+        }
     }
 }
@@ -0,0 +1,530 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use failure::Fallible as Result;
+use failure::Error;
+use serde_json::Value;
+
+use librepology::v1::types::Name;
+use librepology::v1::types::Package;
+use librepology::v1::types::Problem;
+use librepology::v1::types::Repo;
+use librepology::v1::types::Status;
+use librepology::v1::types::Version;
+use librepology::v1::vercmp;
+
+use crate::compare::CompareResult;
+use crate::output::Kind;
+
+/// The state of a package, as far as changes are concerned
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct PackageState {
+    project: Option<String>,
+    repo: Repo,
+    name: Option<Name>,
+    version: Version,
+    status: Option<Status>,
+}
+
+impl PackageState {
+    pub fn from_package(project: Option<String>, package: &Package) -> Self {
+        PackageState {
+            project,
+            repo: package.repo().clone(),
+            name: package.any_name().cloned(),
+            version: package.version().clone(),
+            status: package.status().cloned(),
+        }
+    }
+}
+
+/// How a package changed
+#[derive(Serialize, Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ChangeKind {
+    #[serde(rename = "appeared")]
+    Appeared,
+
+    #[serde(rename = "disappeared")]
+    Disappeared,
+
+    #[serde(rename = "changed")]
+    Changed,
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChangeKind::Appeared    => write!(f, "appeared"),
+            ChangeKind::Disappeared => write!(f, "disappeared"),
+            ChangeKind::Changed     => write!(f, "changed"),
+        }
+    }
+}
+
+/// A package which appeared, disappeared or changed its version or status
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct PackageChange {
+    project: Option<String>,
+    repo: Repo,
+    name: Option<Name>,
+    change: ChangeKind,
+    old_version: Option<Version>,
+    new_version: Option<Version>,
+    old_status: Option<Status>,
+    new_status: Option<Status>,
+}
+
+impl PackageChange {
+    pub fn project(&self) -> Option<&String> {
+        self.project.as_ref()
+    }
+
+    pub fn repo(&self) -> &Repo {
+        &self.repo
+    }
+
+    pub fn name(&self) -> Option<&Name> {
+        self.name.as_ref()
+    }
+
+    pub fn change(&self) -> ChangeKind {
+        self.change
+    }
+
+    pub fn old_version(&self) -> Option<&Version> {
+        self.old_version.as_ref()
+    }
+
+    pub fn new_version(&self) -> Option<&Version> {
+        self.new_version.as_ref()
+    }
+
+    pub fn old_status(&self) -> Option<&Status> {
+        self.old_status.as_ref()
+    }
+
+    pub fn new_status(&self) -> Option<&Status> {
+        self.new_status.as_ref()
+    }
+}
+
+/// How a problem changed
+#[derive(Serialize, Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ProblemChangeKind {
+    #[serde(rename = "new")]
+    New,
+
+    #[serde(rename = "fixed")]
+    Fixed,
+}
+
+impl fmt::Display for ProblemChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProblemChangeKind::New   => write!(f, "new"),
+            ProblemChangeKind::Fixed => write!(f, "fixed"),
+        }
+    }
+}
+
+/// A problem which is new or was fixed
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct ProblemChange {
+    change: ProblemChangeKind,
+
+    #[serde(flatten)]
+    problem: Problem,
+}
+
+impl ProblemChange {
+    pub fn change(&self) -> ProblemChangeKind {
+        self.change
+    }
+
+    pub fn problem(&self) -> &Problem {
+        &self.problem
+    }
+}
+
+/// All changes between two states
+#[derive(Serialize, Debug, Clone, Eq, PartialEq, Default)]
+pub struct ChangeSet {
+    packages: Vec<PackageChange>,
+    problems: Vec<ProblemChange>,
+}
+
+impl ChangeSet {
+    pub fn new(packages: Vec<PackageChange>, problems: Vec<ProblemChange>) -> Self {
+        ChangeSet { packages, problems }
+    }
+
+    pub fn packages(&self) -> &Vec<PackageChange> {
+        &self.packages
+    }
+
+    pub fn problems(&self) -> &Vec<ProblemChange> {
+        &self.problems
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty() && self.problems.is_empty()
+    }
+
+    /// Merge another change set into this one
+    pub fn extend(&mut self, other: ChangeSet) {
+        self.packages.extend(other.packages);
+        self.problems.extend(other.problems);
+    }
+}
+
+type PackageKey = (Option<String>, Repo, Option<Name>);
+
+/// Index package states by project, repository and name
+///
+/// If there are several packages with the same key, the one with the highest version is used.
+fn index(states: Vec<PackageState>) -> BTreeMap<PackageKey, PackageState> {
+    let mut map : BTreeMap<PackageKey, PackageState> = BTreeMap::new();
+    for state in states {
+        let key = (state.project.clone(), state.repo.clone(), state.name.clone());
+        let is_newer = map
+            .get(&key)
+            .map(|s| vercmp::compare(&state.version, &s.version) == Ordering::Greater)
+            .unwrap_or(true);
+
+        if is_newer {
+            map.insert(key, state);
+        }
+    }
+    map
+}
+
+/// Compute the changes between two lists of package states
+///
+/// The result is sorted by project, repository and name.
+pub fn package_changes(old: Vec<PackageState>, new: Vec<PackageState>) -> Vec<PackageChange> {
+    let old = index(old);
+    let mut new = index(new);
+
+    let mut changes = vec![];
+    for (key, old_state) in old {
+        let new_state = new.remove(&key);
+        let change = match new_state.as_ref() {
+            None => ChangeKind::Disappeared,
+            Some(n) if n.version != old_state.version || n.status != old_state.status => ChangeKind::Changed,
+            Some(_) => continue,
+        };
+
+        let (project, repo, name) = key;
+        changes.push(PackageChange {
+            project,
+            repo,
+            name,
+            change,
+            old_version: Some(old_state.version),
+            new_version: new_state.as_ref().map(|s| s.version.clone()),
+            old_status: old_state.status,
+            new_status: new_state.and_then(|s| s.status),
+        });
+    }
+
+    changes.extend(new.into_iter().map(|((project, repo, name), state)| PackageChange {
+        project,
+        repo,
+        name,
+        change: ChangeKind::Appeared,
+        old_version: None,
+        new_version: Some(state.version),
+        old_status: None,
+        new_status: state.status,
+    }));
+
+    changes.sort_by(|a, b| {
+        (&a.project, &a.repo, &a.name).cmp(&(&b.project, &b.repo, &b.name))
+    });
+    changes
+}
+
+/// Compute the problems which are new and which were fixed
+pub fn problem_changes(old: &[Problem], new: &[Problem]) -> Vec<ProblemChange> {
+    let fixed = old.iter()
+        .filter(|p| !new.contains(p))
+        .map(|p| ProblemChange { change: ProblemChangeKind::Fixed, problem: p.clone() });
+
+    let added = new.iter()
+        .filter(|p| !old.contains(p))
+        .map(|p| ProblemChange { change: ProblemChangeKind::New, problem: p.clone() });
+
+    added.chain(fixed).collect()
+}
+
+/// Data captured from a JSON output of repolocli
+#[derive(Debug, Clone)]
+pub enum Capture {
+    /// An empty list, which could be any kind of data
+    Empty,
+    Packages(Vec<PackageState>),
+    Problems(Vec<Problem>),
+}
+
+impl Capture {
+    /// Parse the JSON output of `project`, `problems` or `compare`
+    ///
+    /// The output of `compare` is captured as packages, the local version of a package is listed
+    /// in the repository "local". Outputs of older versions, without the envelope, are read as
+    /// well.
+    pub fn from_json(s: &str) -> Result<Self> {
+        let value : Value = serde_json::from_str(s)?;
+
+        // A list of packages is the output of `project` with a single name, which is in the query
+        let project_name = match value.pointer("/query/parameters/project_name") {
+            Some(Value::Array(names)) if names.len() == 1 => names[0].as_str().map(String::from),
+            _ => None,
+        };
+
+        let (kind, value) = crate::output::open(value)?;
+
+        match kind {
+            None | Some(Kind::Packages) | Some(Kind::Projects) | Some(Kind::Problems) | Some(Kind::Compare) => {},
+            Some(kind) => return Err(format_err!("Cannot diff the output of {}", kind)),
+        }
+
+        match value {
+            // project: map from project name to packages or null
+            Value::Object(_) => {
+                let projects : BTreeMap<String, Option<Vec<Package>>> = serde_json::from_value(value)
+                    .map_err(|e| format_err!("Not an output of 'project', 'problems' or 'compare': {}", e))?;
+
+                let states = projects
+                    .into_iter()
+                    .flat_map(|(name, packages)| {
+                        packages
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(move |p| PackageState::from_package(Some(name.clone()), &p))
+                    })
+                    .collect();
+
+                Ok(Capture::Packages(states))
+            },
+
+            Value::Array(ref elements) => {
+                let first = match elements.first() {
+                    None        => return Ok(Capture::Empty),
+                    Some(first) => first,
+                };
+
+                if first.get("problem").is_some() {
+                    serde_json::from_value(value)
+                        .map(Capture::Problems)
+                        .map_err(Error::from)
+                } else if first.get("comparisons").is_some() {
+                    let compared : Vec<CompareResult> = serde_json::from_value(value)?;
+                    let states = compared
+                        .into_iter()
+                        .flat_map(|c| {
+                            let name = c.package_name().clone();
+                            let local = PackageState {
+                                project: Some(name.clone()),
+                                repo: Repo::new(String::from("local")),
+                                name: Some(Name::new(name.clone())),
+                                version: Version::new(c.local_version().clone()),
+                                status: None,
+                            };
+
+                            let upstream = c.comparisons().clone().into_iter().map(move |t| PackageState {
+                                project: Some(name.clone()),
+                                repo: Repo::new(t.repo().clone()),
+                                name: Some(Name::new(name.clone())),
+                                version: Version::new(t.version().clone()),
+                                status: None,
+                            });
+
+                            std::iter::once(local).chain(upstream)
+                        })
+                        .collect();
+
+                    Ok(Capture::Packages(states))
+                } else {
+                    let packages : Vec<Package> = serde_json::from_value(value)
+                        .map_err(|e| format_err!("Not an output of 'project', 'problems' or 'compare': {}", e))?;
+
+                    Ok(Capture::Packages({
+                        packages
+                            .iter()
+                            .map(|p| PackageState::from_package(project_name.clone(), p))
+                            .collect()
+                    }))
+                }
+            },
+
+            _ => Err(format_err!("Not an output of 'project', 'problems' or 'compare'")),
+        }
+    }
+}
+
+/// Name the packages of a capture without project names after the single project of the other
+///
+/// Outputs of earlier versions for a single project do not contain the name of the project.
+fn name_unnamed(states: &mut [PackageState], other: &[PackageState]) {
+    if states.iter().any(|s| s.project.is_some()) {
+        return
+    }
+
+    let mut names = other.iter().filter_map(|s| s.project.as_ref());
+    if let Some(name) = names.next() {
+        if names.all(|n| n == name) {
+            states.iter_mut().for_each(|s| s.project = Some(name.clone()));
+        }
+    }
+}
+
+/// Compute the changes between two captures
+///
+/// Both captures have to contain the same kind of data.
+pub fn diff(old: Capture, new: Capture) -> Result<ChangeSet> {
+    match (old, new) {
+        (Capture::Empty, Capture::Empty) => Ok(ChangeSet::default()),
+
+        (Capture::Packages(mut old), Capture::Packages(mut new)) => {
+            name_unnamed(&mut old, &new);
+            name_unnamed(&mut new, &old);
+            Ok(ChangeSet::new(package_changes(old, new), vec![]))
+        },
+        (Capture::Packages(old), Capture::Empty)         => Ok(ChangeSet::new(package_changes(old, vec![]), vec![])),
+        (Capture::Empty, Capture::Packages(new))         => Ok(ChangeSet::new(package_changes(vec![], new), vec![])),
+
+        (Capture::Problems(old), Capture::Problems(new)) => Ok(ChangeSet::new(vec![], problem_changes(&old, &new))),
+        (Capture::Problems(old), Capture::Empty)         => Ok(ChangeSet::new(vec![], problem_changes(&old, &[]))),
+        (Capture::Empty, Capture::Problems(new))         => Ok(ChangeSet::new(vec![], problem_changes(&[], &new))),
+
+        _ => Err(format_err!("Cannot diff packages against problems")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capture(json: &str) -> Capture {
+        Capture::from_json(json).unwrap()
+    }
+
+    fn packages(project: &str, versions: &[(&str, &str)]) -> String {
+        let packages = versions
+            .iter()
+            .map(|(repo, version)| format!(r#"{{"repo": "{}", "name": "{}", "version": "{}"}}"#, repo, project, version))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("[{}]", packages)
+    }
+
+    fn projects(project: &str, versions: &[(&str, &str)]) -> String {
+        format!(r#"{{"schema_version": 1, "kind": "projects",
+                    "query": {{"subcommand": "project", "stdin": false, "parameters": {{"project_name": ["{}"]}}}},
+                    "data": {{"{}": {}}}}}"#,
+                project, project, packages(project, versions))
+    }
+
+    fn single_project(project: &str, versions: &[(&str, &str)]) -> String {
+        format!(r#"{{"schema_version": 1, "kind": "packages",
+                    "query": {{"subcommand": "project", "stdin": false, "parameters": {{"project_name": ["{}"]}}}},
+                    "data": {}}}"#,
+                project, packages(project, versions))
+    }
+
+    fn problems(descriptions: &[&str]) -> String {
+        let problems = descriptions
+            .iter()
+            .map(|d| format!(r#"{{"repo": "arch", "name": "foo", "effname": "foo", "maintainer": "me@x", "problem": "{}"}}"#, d))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("[{}]", problems)
+    }
+
+    fn summary(changes: &ChangeSet) -> Vec<(Option<String>, String, ChangeKind)> {
+        changes.packages()
+            .iter()
+            .map(|c| (c.project().cloned(), c.repo().as_str().to_string(), c.change()))
+            .collect()
+    }
+
+    #[test]
+    fn test_no_changes() {
+        let old = capture(&projects("foo", &[("arch", "1.0"), ("debian", "0.9")]));
+        let new = capture(&projects("foo", &[("debian", "0.9"), ("arch", "1.0")]));
+        assert!(diff(old, new).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_package_changes() {
+        let old = capture(&projects("foo", &[("arch", "1.0"), ("debian", "0.9")]));
+        let new = capture(&projects("foo", &[("arch", "1.1"), ("fedora", "1.1")]));
+        let changes = diff(old, new).unwrap();
+
+        assert_eq!(summary(&changes), vec![
+            (Some(String::from("foo")), String::from("arch"), ChangeKind::Changed),
+            (Some(String::from("foo")), String::from("debian"), ChangeKind::Disappeared),
+            (Some(String::from("foo")), String::from("fedora"), ChangeKind::Appeared),
+        ]);
+
+        let arch = &changes.packages()[0];
+        assert_eq!(arch.old_version().map(|v| v.as_str()), Some("1.0"));
+        assert_eq!(arch.new_version().map(|v| v.as_str()), Some("1.1"));
+    }
+
+    #[test]
+    fn test_highest_version_of_duplicates() {
+        let old = capture(&projects("foo", &[("arch", "1.0"), ("arch", "1.2")]));
+        let new = capture(&projects("foo", &[("arch", "1.2")]));
+        assert!(diff(old, new).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_single_project_against_projects() {
+        let single = single_project("foo", &[("arch", "1.0")]);
+        assert!(diff(capture(&single), capture(&projects("foo", &[("arch", "1.0")]))).unwrap().is_empty());
+        assert!(diff(capture(&projects("foo", &[("arch", "1.0")])), capture(&single)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unnamed_against_projects() {
+        let unnamed = packages("foo", &[("arch", "1.0")]);
+        let changes = diff(capture(&unnamed), capture(&projects("foo", &[("arch", "1.1")]))).unwrap();
+        assert_eq!(summary(&changes), vec![(Some(String::from("foo")), String::from("arch"), ChangeKind::Changed)]);
+    }
+
+    #[test]
+    fn test_problem_changes() {
+        let old = capture(&problems(&["dead link", "redirect"]));
+        let new = capture(&problems(&["redirect", "no homepage"]));
+        let changes = diff(old, new).unwrap();
+
+        let changes = changes.problems()
+            .iter()
+            .map(|c| (c.change(), c.problem().problem_description().as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(changes, vec![(ProblemChangeKind::New, "no homepage"), (ProblemChangeKind::Fixed, "dead link")]);
+    }
+
+    #[test]
+    fn test_empty_captures() {
+        let changes = diff(capture("[]"), capture(&projects("foo", &[("arch", "1.0")]))).unwrap();
+        assert_eq!(summary(&changes), vec![(Some(String::from("foo")), String::from("arch"), ChangeKind::Appeared)]);
+
+        let changes = diff(capture(&problems(&["dead link"])), capture("[]")).unwrap();
+        assert_eq!(changes.problems().len(), 1);
+    }
+
+    #[test]
+    fn test_packages_against_problems() {
+        assert!(diff(capture(&projects("foo", &[("arch", "1.0")])), capture(&problems(&["dead link"]))).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_kind() {
+        let summary = r#"{"schema_version": 1, "kind": "problem-summary",
+                          "query": {"subcommand": "problems", "stdin": false, "parameters": {}}, "data": {}}"#;
+        assert!(Capture::from_json(summary).is_err());
+    }
+}
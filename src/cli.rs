@@ -1,4 +1,6 @@
-use clap::{App, Arg, ArgGroup, SubCommand};
+use clap::{App, AppSettings, Arg, ArgGroup, SubCommand};
+
+use crate::problemkind::ProblemKind;
 
 pub fn build_cli<'a>() -> App<'a, 'a> {
     App::new("repolocli")
@@ -40,11 +42,20 @@ pub fn build_cli<'a>() -> App<'a, 'a> {
             .required(false)
             .multiple(false)
             .takes_value(true)
-            .possible_values(&["table", "json", "lines"])
+            .possible_values(&["table", "json", "ndjson", "yaml", "toml", "lines", "csv", "tsv", "markdown", "html", "openmetrics", "junit", "template"])
             .default_value("lines")
             .help("Output format")
         )
 
+        .arg(Arg::with_name("template")
+            .long("template")
+            .required_if("output", "template")
+            .multiple(false)
+            .takes_value(true)
+            .value_name("FILE")
+            .help("Handlebars template to render the output with, for '--output template'")
+        )
+
         .arg(Arg::with_name("input_stdin")
             .long("stdin")
             .short("I")
@@ -58,43 +69,99 @@ pub fn build_cli<'a>() -> App<'a, 'a> {
             .arg(Arg::with_name("project_name")
                 .index(1)
                 .required(false) // TODO: Make required, is not required currently when --stdin is passed.
+                .multiple(true)
+                .takes_value(true)
+                .value_name("NAME")
+                .help("Query data about one or more projects. Pass '-' to read a newline-separated list of names from stdin")
+            )
+            .arg(Arg::with_name("from-file")
+                .long("from-file")
+                .required(false)
                 .multiple(false)
                 .takes_value(true)
-                .help("Query data about a project")
+                .value_name("FILE")
+                .help("Read a newline-separated list of project names from FILE ('-' for stdin)")
             )
 
+            .arg(Arg::with_name("sort")
+                .long("sort")
+                .required(false)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("KEYS")
+                .allow_hyphen_values(true)
+                .help("Sort output by a comma-separated list of keys, prefix a key with '-' to sort descending. Keys: repo, name, srcname, binname, visiblename, version, status, summary, licenses, maintainers, www, downloads")
+            )
             .arg(Arg::with_name("sort-version")
                 .long("sort-version")
                 .required(false)
                 .multiple(false)
                 .takes_value(false)
-                .help("Sort output by version")
+                .hidden(true)
+                .conflicts_with("sort")
                 .conflicts_with("sort-repo")
+                .help("Deprecated, use --sort version")
             )
             .arg(Arg::with_name("sort-repo")
                 .long("sort-repo")
                 .required(false)
                 .multiple(false)
                 .takes_value(false)
-                .help("Sort output by repository")
+                .hidden(true)
+                .conflicts_with("sort")
                 .conflicts_with("sort-version")
+                .help("Deprecated, use --sort repo")
+            )
+            .arg(Arg::with_name("columns")
+                .long("columns")
+                .required(false)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("COLUMNS")
+                .help("Comma-separated list of columns to print with the lines, table, csv, tsv, markdown and html output. Columns: project, repo, name, srcname, binname, visiblename, version, status, summary, licenses, maintainers, www, downloads")
             )
             .arg(Arg::with_name("latest")
                 .long("latest")
                 .required(false)
                 .multiple(false)
                 .takes_value(false)
-                .help("Try to find the lastest version (version is string-compared if not used with --semver)")
-                .conflicts_with("sort-version")
-                .conflicts_with("sort-repo")
+                .help("Only list the packages which carry the latest version")
+            )
+            .arg(Arg::with_name("latest-by")
+                .long("latest-by")
+                .required(false)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("STRATEGY")
+                .possible_values(&["status", "version", "semver", "string"])
+                .requires("latest")
+                .help("How to find the latest version: Use what repology marks as newest (status, default), compare versions like repology does (version), as semver, ignoring non-semver versions (semver) or as plain strings (string)")
             )
             .arg(Arg::with_name("semver")
                 .long("semver")
                 .required(false)
                 .multiple(false)
                 .takes_value(false)
+                .hidden(true)
                 .requires("latest")
-                .help("Try to find latest version using semver. If semver could not be parsed, equality is assumed, which might yield bogus results.")
+                .conflicts_with("latest-by")
+                .help("Deprecated, use --latest-by semver")
+            )
+            .arg(Arg::with_name("include-devel")
+                .long("include-devel")
+                .required(false)
+                .multiple(false)
+                .takes_value(false)
+                .requires("latest")
+                .help("Consider development versions when finding the latest version")
+            )
+            .arg(Arg::with_name("summary")
+                .long("summary")
+                .required(false)
+                .multiple(false)
+                .takes_value(false)
+                .help("Print summary statistics of the project instead of the list of packages")
+                .conflicts_with("latest")
             )
         )
 
@@ -124,22 +191,303 @@ pub fn build_cli<'a>() -> App<'a, 'a> {
                 .required(true))
 
 
+            .arg(Arg::with_name("sort")
+                .long("sort")
+                .required(false)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("KEYS")
+                .allow_hyphen_values(true)
+                .help("Sort output by a comma-separated list of keys, prefix a key with '-' to sort descending. Keys: repo, name, effname, maintainer, problem")
+            )
             .arg(Arg::with_name("sort-maintainer")
                 .long("sort-maintainer")
                 .required(false)
                 .multiple(false)
                 .takes_value(false)
-                .help("Sort output by maintainer")
+                .hidden(true)
+                .conflicts_with("sort")
                 .conflicts_with("sort-repo")
+                .help("Deprecated, use --sort maintainer")
             )
             .arg(Arg::with_name("sort-repo")
                 .long("sort-repo")
                 .required(false)
                 .multiple(false)
                 .takes_value(false)
-                .help("Sort output by repository")
+                .hidden(true)
+                .conflicts_with("sort")
                 .conflicts_with("sort-maintainer")
+                .help("Deprecated, use --sort repo")
+            )
+            .arg(Arg::with_name("columns")
+                .long("columns")
+                .required(false)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("COLUMNS")
+                .help("Comma-separated list of columns to print with the lines, table, csv, tsv, markdown and html output. Columns: repo, name, effname, maintainer, kind, problem")
+            )
+
+            .arg(Arg::with_name("group-by")
+                .long("group-by")
+                .required(false)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("GROUP")
+                .possible_values(&["repo", "maintainer", "kind", "project"])
+                .help("Group problems, with the number of problems per group and kind")
+            )
+
+            .arg(Arg::with_name("summary")
+                .long("summary")
+                .required(false)
+                .multiple(false)
+                .takes_value(false)
+                .conflicts_with("group-by")
+                .help("Only print the number of problems per kind and per maintainer")
+            )
+
+            .arg(Arg::with_name("show-acked")
+                .long("show-acked")
+                .required(false)
+                .multiple(false)
+                .takes_value(false)
+                .help("Also list acknowledged problems")
+            )
+
+            .setting(AppSettings::SubcommandsNegateReqs)
+            .subcommand(SubCommand::with_name("ack")
+                .about("Acknowledge a problem, so it is not listed anymore")
+                .args(&problem_id_args())
+                .arg(Arg::with_name("reason")
+                    .long("reason")
+                    .required(false)
+                    .multiple(false)
+                    .takes_value(true)
+                    .value_name("REASON")
+                    .help("Why the problem is acknowledged")
+                )
+                .after_help(r#"
+            The acknowledgement expires when the description of the problem changes, so new
+            issues with the package are listed again.
+            "#)
+            )
+
+            .subcommand(SubCommand::with_name("unack")
+                .about("Remove the acknowledgement of a problem")
+                .args(&problem_id_args())
+            )
+        )
+
+        .subcommand(SubCommand::with_name("search")
+            .about("Search for projects by name")
+            .arg(Arg::with_name("term")
+                .index(1)
+                .required(true)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("TERM")
+                .help("Search for projects with TERM in their name")
+            )
+            .arg(Arg::with_name("repo")
+                .short("r")
+                .long("repo")
+                .alias("repository")
+                .required(false)
+                .multiple(true)
+                .takes_value(true)
+                .number_of_values(1)
+                .value_name("REPO")
+                .help("Only list projects present in this repository (can be passed multiple times)")
+            )
+        )
+
+        .subcommand(SubCommand::with_name("outdated")
+            .about("List all outdated packages in a repository")
+            .arg(Arg::with_name("repo")
+                .short("r")
+                .long("repo")
+                .alias("repository")
+                .required(true)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("REPO")
+                .help("The repository to list outdated packages for")
+            )
+            .arg(Arg::with_name("maintainer")
+                .short("m")
+                .long("maintainer")
+                .alias("maint")
+                .required(false)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("MAINTAINER")
+                .help("Only list outdated packages of this maintainer")
+            )
+        )
+
+        .subcommand(SubCommand::with_name("missing")
+            .about("List projects which are packaged in reference repositories but missing in a repository")
+            .arg(Arg::with_name("repo")
+                .short("r")
+                .long("repo")
+                .alias("repository")
+                .required(true)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("REPO")
+                .help("The repository to find missing projects for")
+            )
+            .arg(Arg::with_name("reference")
+                .long("reference")
+                .alias("ref")
+                .required(true)
+                .multiple(true)
+                .takes_value(true)
+                .min_values(1)
+                .value_name("REPO")
+                .help("The repositories to compare to")
+            )
+            .arg(Arg::with_name("newest")
+                .long("newest")
+                .required(false)
+                .multiple(false)
+                .takes_value(false)
+                .help("Only list projects which have the newest version in a reference repository")
+            )
+            .after_help(r#"
+            Projects which are packaged in more of the reference repositories are listed first.
+            "#)
+        )
+
+        .subcommand(SubCommand::with_name("repo-diff")
+            .about("Compare the versions of all projects in two repositories")
+            .arg(Arg::with_name("repo_a")
+                .index(1)
+                .required(true)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("A")
+                .help("The first repository")
+            )
+            .arg(Arg::with_name("repo_b")
+                .index(2)
+                .required(true)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("B")
+                .help("The second repository")
+            )
+            .after_help(r#"
+            Each project is classified as one of:
+
+            * a-newer: A has a newer version than B
+            * b-newer: B has a newer version than A
+            * equal:   A and B have the same version
+            * only-a:  The project is only packaged in A
+            * only-b:  The project is only packaged in B
+            "#)
+        )
+
+        .subcommand(SubCommand::with_name("maintainer")
+            .about("List all packages of a maintainer, their status and problems")
+            .arg(Arg::with_name("maintainer")
+                .index(1)
+                .required(true)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("MAINTAINER")
+                .help("The maintainer (usually an email address)")
+            )
+        )
+
+        .subcommand(SubCommand::with_name("snapshot")
+            .about("Record the current state of projects in the local snapshot store")
+            .arg(Arg::with_name("project_name")
+                .index(1)
+                .required(false)
+                .multiple(true)
+                .takes_value(true)
+                .value_name("NAME")
+                .help("The projects to record, defaults to the watchlist from the configuration")
+            )
+        )
+
+        .subcommand(SubCommand::with_name("history")
+            .about("Show when the versions of a project changed, based on recorded snapshots")
+            .arg(Arg::with_name("project_name")
+                .index(1)
+                .required(true)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("NAME")
+                .help("The project to show the history of")
+            )
+        )
+
+        .subcommand(SubCommand::with_name("diff")
+            .about("Show the changes between two JSON outputs of repolocli")
+            .arg(Arg::with_name("old")
+                .index(1)
+                .required(true)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("OLD")
+                .help("The older JSON output")
+            )
+            .arg(Arg::with_name("new")
+                .index(2)
+                .required(true)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("NEW")
+                .help("The newer JSON output")
             )
+            .after_help(r#"
+            Diff two outputs of 'repolocli --output json' for 'project', 'problems' or 'compare',
+            for example captured at different times. Does not access the network.
+
+            Packages are reported as appeared, disappeared or changed (version or status),
+            problems as new or fixed.
+            "#)
+        )
+
+        .subcommand(SubCommand::with_name("watch")
+            .about("Poll projects and problems repeatedly and run a command on changes")
+            .arg(Arg::with_name("interval")
+                .long("interval")
+                .required(false)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("DURATION")
+                .help("Time between two polls, for example '30m' or '6h'. Defaults to the interval from the configuration")
+            )
+            .arg(Arg::with_name("command")
+                .long("command")
+                .required(false)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("COMMAND")
+                .help("Command to run on changes, overrides the command from the configuration")
+            )
+            .arg(Arg::with_name("once")
+                .long("once")
+                .required(false)
+                .multiple(false)
+                .takes_value(false)
+                .help("Poll only once, for example when running from cron")
+            )
+            .after_help(r#"
+            Polls the projects and problems configured in the [watch] section of the
+            configuration and compares them with the state stored by the previous poll.
+
+            On changes, the changes are printed and the command is run with 'sh -c', with the
+            changes as JSON (like 'repolocli --output json diff') on stdin. If the command fails,
+            the changes are reported again in the next poll.
+
+            Projects or problems which cannot be fetched are skipped and compared in the next poll.
+            "#)
         )
 
         .subcommand(SubCommand::with_name("compare")
@@ -158,6 +506,13 @@ pub fn build_cli<'a>() -> App<'a, 'a> {
                 .takes_value(true)
                 .value_name("DIST")
                 .help("A list of repology distribution names to compare to"))
+            .arg(Arg::with_name("columns")
+                .long("columns")
+                .required(false)
+                .multiple(false)
+                .takes_value(true)
+                .value_name("COLUMNS")
+                .help("Comma-separated list of columns to print with the lines, table, csv, tsv, markdown and html output. Columns: package_name, local_version, repo, version, status"))
 
             .after_help(r#"
             Compare a list of packages to all supplied repology distributions.
@@ -172,10 +527,50 @@ pub fn build_cli<'a>() -> App<'a, 'a> {
             "#)
         )
 
+        .subcommand(SubCommand::with_name("schema")
+            .about("Print the JSON Schema of the JSON output")
+            .after_help(r#"
+            Prints the JSON Schema (draft-07) of the output of 'repolocli --output json'.
+            The schema is versioned by the "schema_version" field of the output.
+            "#)
+        )
+
         .after_help(r#"
         repolocli can read data from stdin, if you want to postprocess repology.org data you already
         fetched from repology.org/api/v1 via curl (or some other method).
         In this case, repolocli is only a easier-to-use 'jq' (if you don't know jq, look it up NOW!).
+        The JSON output of repolocli ('--output json') can be read from stdin as well, for chaining
+        invocations of repolocli.
         "#)
 
 }
+
+/// Arguments identifying a problem, for acknowledging problems
+fn problem_id_args<'a>() -> Vec<Arg<'a, 'a>> {
+    vec![
+        Arg::with_name("repo")
+            .index(1)
+            .required(true)
+            .multiple(false)
+            .takes_value(true)
+            .value_name("REPO")
+            .help("The repository of the problem"),
+
+        Arg::with_name("name")
+            .index(2)
+            .required(true)
+            .multiple(false)
+            .takes_value(true)
+            .value_name("NAME")
+            .help("The name of the package with the problem"),
+
+        Arg::with_name("kind")
+            .index(3)
+            .required(true)
+            .multiple(false)
+            .takes_value(true)
+            .value_name("KIND")
+            .possible_values(ProblemKind::NAMES)
+            .help("The kind of the problem"),
+    ]
+}
@@ -1,3 +1,17 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Deref;
+
+use failure::Fallible as Result;
+
+use librepology::v1::api::Api;
+use librepology::v1::types::Package;
+use librepology::v1::types::Repo;
+use librepology::v1::types::Version;
+use librepology::v1::vercmp;
+
+use crate::backend::Backend;
+
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
 pub struct ComparePackage {
     name: String,
@@ -12,4 +26,183 @@ impl ComparePackage {
     pub fn version(&self) -> &String {
         &self.version
     }
-}
\ No newline at end of file
+}
+
+/// How the local version of a package compares to the version in a repository
+#[derive(Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CompareStatus {
+    /// The repository has a newer version
+    #[serde(rename = "behind")]
+    Behind,
+
+    #[serde(rename = "equal")]
+    Equal,
+
+    /// The local version is newer
+    #[serde(rename = "ahead")]
+    Ahead,
+
+    /// The repository does not have the package
+    #[serde(rename = "missing")]
+    Missing,
+}
+
+impl fmt::Display for CompareStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompareStatus::Behind  => write!(f, "behind"),
+            CompareStatus::Equal   => write!(f, "equal"),
+            CompareStatus::Ahead   => write!(f, "ahead"),
+            CompareStatus::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+/// A local package compared to one repository
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct CompareRow {
+    package_name: String,
+    local_version: String,
+    repo: Repo,
+    version: Option<Version>,
+    status: CompareStatus,
+}
+
+impl CompareRow {
+    pub fn package_name(&self) -> &String {
+        &self.package_name
+    }
+
+    pub fn local_version(&self) -> &String {
+        &self.local_version
+    }
+
+    pub fn repo(&self) -> &Repo {
+        &self.repo
+    }
+
+    pub fn version(&self) -> Option<&Version> {
+        self.version.as_ref()
+    }
+
+    pub fn status(&self) -> CompareStatus {
+        self.status
+    }
+}
+
+/// Compare local packages to the packages in some repositories
+///
+/// There is one row per package in a repository, and one row with status `missing` for each
+/// repository which does not have the package at all.
+pub fn compare_rows(packages: &[ComparePackage], backend: &Backend, repos: &[Repo]) -> Result<Vec<CompareRow>> {
+    let mut rows = vec![];
+
+    for package in packages {
+        let upstream = backend.project(package.name())?;
+        rows.append(&mut package_rows(package, &upstream, repos));
+    }
+
+    Ok(rows)
+}
+
+/// Compare one local package to its packages in some repositories, see `compare_rows()`
+pub fn package_rows(package: &ComparePackage, upstream: &[Package], repos: &[Repo]) -> Vec<CompareRow> {
+    let mut rows = vec![];
+
+    for repo in repos {
+        let in_repo = upstream.iter().filter(|p| p.repo() == repo).collect::<Vec<_>>();
+        if in_repo.is_empty() {
+            rows.push(CompareRow {
+                package_name: package.name().clone(),
+                local_version: package.version().clone(),
+                repo: repo.clone(),
+                version: None,
+                status: CompareStatus::Missing,
+            });
+        }
+
+        rows.extend(in_repo.into_iter().map(|p| {
+            let status = match vercmp::compare(package.version(), p.version()) {
+                Ordering::Less    => CompareStatus::Behind,
+                Ordering::Equal   => CompareStatus::Equal,
+                Ordering::Greater => CompareStatus::Ahead,
+            };
+
+            CompareRow {
+                package_name: package.name().clone(),
+                local_version: package.version().clone(),
+                repo: repo.clone(),
+                version: Some(p.version().clone()),
+                status,
+            }
+        }));
+    }
+
+    rows
+}
+
+/// A local package with the versions in the compared repositories
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct CompareResult {
+    package_name: String,
+    local_version: String,
+    comparisons: Vec<CompareTarget>,
+}
+
+impl CompareResult {
+    pub fn package_name(&self) -> &String {
+        &self.package_name
+    }
+
+    pub fn local_version(&self) -> &String {
+        &self.local_version
+    }
+
+    pub fn comparisons(&self) -> &Vec<CompareTarget> {
+        &self.comparisons
+    }
+}
+
+/// The version of a package in one repository
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct CompareTarget {
+    version: String,
+    repo: String,
+}
+
+impl CompareTarget {
+    pub fn version(&self) -> &String {
+        &self.version
+    }
+
+    pub fn repo(&self) -> &String {
+        &self.repo
+    }
+}
+
+/// Compare local packages to the packages in some repositories, one result per local package
+pub fn compare_results(packages: &[ComparePackage], backend: &Backend, repos: &[Repo]) -> Result<Vec<CompareResult>> {
+    packages
+        .iter()
+        .map(|package| compare_result(package, backend, repos))
+        .collect()
+}
+
+/// Compare one package to the versions in `repos`
+pub fn compare_result(package: &ComparePackage, backend: &Backend, repos: &[Repo]) -> Result<CompareResult> {
+    let comparisons = backend
+        .project(package.name())?
+        .into_iter()
+        .filter(|p| repos.contains(p.repo()))
+        .map(|p| CompareTarget {
+            version: p.version().deref().clone(),
+            repo: p.repo().deref().clone(),
+        })
+        .collect();
+
+    Ok(CompareResult {
+        package_name: package.name().clone(),
+        local_version: package.version().clone(),
+        comparisons,
+    })
+}
@@ -1,3 +1,6 @@
+use std::time::Duration;
+
+use failure::Fallible as Result;
 use url::Url;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -11,6 +14,30 @@ pub struct Configuration {
 
     #[serde(rename = "blacklist")]
     blacklist: Vec<String>,
+
+    #[serde(rename = "sort")]
+    #[serde(default)]
+    sort: SortConfig,
+
+    #[serde(rename = "watchlist")]
+    #[serde(default)]
+    watchlist: Vec<String>,
+
+    #[serde(rename = "cache")]
+    #[serde(default)]
+    cache: CacheConfig,
+
+    #[serde(rename = "watch")]
+    #[serde(default)]
+    watch: WatchConfig,
+
+    #[serde(rename = "csv")]
+    #[serde(default)]
+    csv: CsvConfig,
+
+    #[serde(rename = "columns")]
+    #[serde(default)]
+    columns: ColumnsConfig,
 }
 
 impl Configuration {
@@ -26,5 +53,167 @@ impl Configuration {
         &self.blacklist
     }
 
+    pub fn sort(&self) -> &SortConfig {
+        &self.sort
+    }
+
+    pub fn watchlist(&self) -> &Vec<String> {
+        &self.watchlist
+    }
+
+    pub fn cache(&self) -> &CacheConfig {
+        &self.cache
+    }
+
+    pub fn watch(&self) -> &WatchConfig {
+        &self.watch
+    }
+
+    pub fn csv(&self) -> &CsvConfig {
+        &self.csv
+    }
+
+    pub fn columns(&self) -> &ColumnsConfig {
+        &self.columns
+    }
+
+}
+
+/// Default sort specifications for the subcommands, used if `--sort` is not passed
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SortConfig {
+    #[serde(rename = "project")]
+    project: Option<String>,
+
+    #[serde(rename = "problems")]
+    problems: Option<String>,
+}
+
+impl SortConfig {
+    pub fn project(&self) -> Option<&String> {
+        self.project.as_ref()
+    }
+
+    pub fn problems(&self) -> Option<&String> {
+        self.problems.as_ref()
+    }
+}
+
+/// Default columns for the subcommands, used if `--columns` is not passed
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ColumnsConfig {
+    #[serde(rename = "project")]
+    project: Option<String>,
+
+    #[serde(rename = "problems")]
+    problems: Option<String>,
+
+    #[serde(rename = "compare")]
+    compare: Option<String>,
 }
 
+impl ColumnsConfig {
+    pub fn project(&self) -> Option<&String> {
+        self.project.as_ref()
+    }
+
+    pub fn problems(&self) -> Option<&String> {
+        self.problems.as_ref()
+    }
+
+    pub fn compare(&self) -> Option<&String> {
+        self.compare.as_ref()
+    }
+}
+
+/// Parse a duration like "90s", "30m" or "6h"
+pub fn parse_duration(s: &str) -> Result<Duration> {
+    humantime::parse_duration(s).map_err(|e| format_err!("Invalid duration '{}': {}", s, e))
+}
+
+/// Caching of responses from repology
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CacheConfig {
+    /// How long responses are reused, caching is disabled if not set
+    #[serde(rename = "ttl")]
+    ttl: Option<String>,
+}
+
+impl CacheConfig {
+    pub fn ttl(&self) -> Result<Option<Duration>> {
+        self.ttl.as_ref().map(|s| parse_duration(s)).transpose()
+    }
+}
+
+/// What "repolocli watch" polls and what it does on changes
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct WatchConfig {
+    /// Time between two polls, used if "--interval" is not passed
+    #[serde(rename = "interval")]
+    interval: Option<String>,
+
+    /// Command to run on changes, with the changes as JSON on stdin
+    #[serde(rename = "command")]
+    command: Option<String>,
+
+    /// Projects to watch, the watchlist is used if not set
+    #[serde(rename = "projects")]
+    projects: Option<Vec<String>>,
+
+    /// Problems to watch
+    #[serde(rename = "problems")]
+    #[serde(default)]
+    problems: Vec<ProblemScope>,
+}
+
+impl WatchConfig {
+    pub fn interval(&self) -> Option<&String> {
+        self.interval.as_ref()
+    }
+
+    pub fn command(&self) -> Option<&String> {
+        self.command.as_ref()
+    }
+
+    pub fn projects(&self) -> Option<&Vec<String>> {
+        self.projects.as_ref()
+    }
+
+    pub fn problems(&self) -> &Vec<ProblemScope> {
+        &self.problems
+    }
+}
+
+/// The problems of a repository or of a maintainer, or of a maintainer in one repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProblemScope {
+    #[serde(rename = "repo")]
+    repo: Option<String>,
+
+    #[serde(rename = "maintainer")]
+    maintainer: Option<String>,
+}
+
+impl ProblemScope {
+    pub fn repo(&self) -> Option<&String> {
+        self.repo.as_ref()
+    }
+
+    pub fn maintainer(&self) -> Option<&String> {
+        self.maintainer.as_ref()
+    }
+}
+
+/// Settings for the CSV and TSV output
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CsvConfig {
+    /// Separator for fields with several values, like the maintainers of a package
+    #[serde(rename = "list_separator")]
+    list_separator: Option<String>,
+}
+
+impl CsvConfig {
+    pub fn list_separator(&self) -> &str {
+        self.list_separator.as_deref().unwrap_or(";")
+    }
+}
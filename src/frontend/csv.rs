@@ -0,0 +1,197 @@
+use std::io::Stdout;
+use std::io::Write;
+
+use librepology::v1::types::Package;
+use librepology::v1::types::Problem;
+use librepology::v1::types::Repo;
+use librepology::v1::project::Project;
+use failure::Fallible as Result;
+
+use crate::frontend::Frontend;
+use crate::frontend::tabular;
+use crate::frontend::tabular::Columns;
+use crate::frontend::tabular::DefaultColumns;
+use crate::frontend::tabular::Section;
+use crate::backend::Backend;
+use crate::compare::ComparePackage;
+use crate::outdated::OutdatedPackage;
+use crate::missing::MissingProject;
+use crate::repodiff::RepoDiffEntry;
+use crate::maintainer::MaintainerOverview;
+use crate::history::HistoryEntry;
+use crate::changes::ChangeSet;
+use crate::problemreport::GroupBy;
+use crate::problemreport::ProblemGroup;
+use crate::problemreport::ProblemSummary;
+
+/// A Frontend that prints comma- or tab-separated values
+///
+/// Every view has a header row with all columns, unless columns are selected. Fields with several
+/// values (like the maintainers of a package) are joined with the configured list separator.
+///
+/// Views with several tables (like the maintainer overview) print one table after the other,
+/// separated by an empty line. Consecutive tables with the same header are printed as one table.
+pub struct CsvFrontend {
+    stdout: Stdout,
+    delimiter: u8,
+    list_separator: String,
+    columns: Option<Columns>,
+}
+
+impl CsvFrontend {
+    pub fn new(stdout: Stdout, delimiter: u8, list_separator: String, columns: Option<Columns>) -> Self {
+        CsvFrontend { stdout, delimiter, list_separator, columns }
+    }
+
+    fn write(&self, sections: Vec<Section>) -> Result<()> {
+        let sections = tabular::select(sections, self.columns.as_ref(), DefaultColumns::All)?;
+        self.write_to(&mut self.stdout.lock(), &sections)
+    }
+
+    fn write_to<W: Write>(&self, mut outlock: W, sections: &[Section]) -> Result<()> {
+        let mut previous_headers : Option<&Vec<String>> = None;
+
+        for section in tabular::printable(sections) {
+            let same_headers = previous_headers == Some(section.headers());
+            if previous_headers.is_some() && !same_headers {
+                writeln!(outlock)?;
+            }
+
+            let mut writer = ::csv::WriterBuilder::new()
+                .delimiter(self.delimiter)
+                .from_writer(&mut outlock);
+
+            if !same_headers {
+                writer.write_record(section.headers())?;
+            }
+
+            section.rows().iter().try_for_each(|row| {
+                writer.write_record(row.iter().map(|cell| cell.plain(&self.list_separator)))
+            })?;
+            writer.flush()?;
+            previous_headers = Some(section.headers());
+        }
+
+        Ok(())
+    }
+}
+
+impl Frontend for CsvFrontend {
+    fn list_packages(&self, packages: Vec<Package>) -> Result<()> {
+        self.write(tabular::packages(&packages))
+    }
+
+    fn list_projects(&self, projects: Vec<Project>) -> Result<()> {
+        self.write(tabular::projects(&projects))
+    }
+
+    fn project_summary(&self, projects: Vec<Project>) -> Result<()> {
+        self.write(tabular::project_summary(&projects))
+    }
+
+    fn list_search_results(&self, projects: Vec<Project>) -> Result<()> {
+        self.write(tabular::search_results(&projects))
+    }
+
+    fn list_outdated(&self, packages: Vec<OutdatedPackage>) -> Result<()> {
+        self.write(tabular::outdated(&packages))
+    }
+
+    fn list_missing(&self, projects: Vec<MissingProject>) -> Result<()> {
+        self.write(tabular::missing(&projects))
+    }
+
+    fn repo_diff(&self, repo_a: &Repo, repo_b: &Repo, entries: Vec<RepoDiffEntry>) -> Result<()> {
+        self.write(tabular::repo_diff(repo_a, repo_b, &entries))
+    }
+
+    fn maintainer_overview(&self, overview: MaintainerOverview) -> Result<()> {
+        self.write(tabular::maintainer_overview(&overview))
+    }
+
+    fn list_history(&self, entries: Vec<HistoryEntry>) -> Result<()> {
+        self.write(tabular::history(&entries))
+    }
+
+    fn list_changes(&self, changes: ChangeSet) -> Result<()> {
+        self.write(tabular::changes(&changes))
+    }
+
+    fn list_problem_groups(&self, group_by: GroupBy, groups: Vec<ProblemGroup>) -> Result<()> {
+        self.write(tabular::problem_groups(group_by, &groups))
+    }
+
+    fn problem_summary(&self, summary: ProblemSummary) -> Result<()> {
+        self.write(tabular::problem_summary(&summary))
+    }
+
+    fn list_problems(&self, problems: Vec<Problem>) -> Result<()> {
+        self.write(tabular::problems(&problems))
+    }
+
+    fn compare_packages(&self, packages: Vec<ComparePackage>, backend: &Backend, filter_repos: Vec<Repo>) -> Result<()> {
+        let rows = crate::compare::compare_rows(&packages, backend, &filter_repos)?;
+        self.write(tabular::compare(&rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::problemreport::group;
+
+    fn write(delimiter: u8, sections: Vec<Section>) -> String {
+        let frontend = CsvFrontend::new(std::io::stdout(), delimiter, String::from(" "), None);
+        let mut buffer = vec![];
+        frontend.write_to(&mut buffer, &sections).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    fn problems(problems: &[(&str, &str)]) -> Vec<Problem> {
+        let problems = problems
+            .iter()
+            .map(|(maintainer, description)| {
+                format!(r#"{{"repo": "arch", "name": "foo", "effname": "foo", "maintainer": "{}", "problem": {}}}"#,
+                        maintainer, serde_json::to_string(description).unwrap())
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        serde_json::from_str(&format!("[{}]", problems)).unwrap()
+    }
+
+    #[test]
+    fn test_quoting() {
+        let problems = problems(&[("me@x", r#"Homepage link "http://foo.org" is dead, for a month"#)]);
+        assert_eq!(write(b',', tabular::problems(&problems)),
+                   "repo,name,effname,maintainer,kind,problem\n\
+                    arch,foo,foo,me@x,homepage-dead,\"Homepage link \"\"http://foo.org\"\" is dead, for a month\"\n");
+    }
+
+    #[test]
+    fn test_tsv() {
+        let problems = problems(&[("me@x", "Something, else")]);
+        assert_eq!(write(b'\t', tabular::problems(&problems)),
+                   "repo\tname\teffname\tmaintainer\tkind\tproblem\n\
+                    arch\tfoo\tfoo\tme@x\tother\tSomething, else\n");
+    }
+
+    #[test]
+    fn test_list_separator() {
+        let packages : Vec<Package> = serde_json::from_str(
+            r#"[{"repo": "arch", "name": "foo", "version": "1.0", "maintainers": ["a@x", "b@x"]}]"#
+        ).unwrap();
+        let output = write(b',', tabular::packages(&packages));
+        assert_eq!(output.lines().nth(1).unwrap(), "arch,foo,,,,1.0,,,,a@x b@x,,");
+    }
+
+    #[test]
+    fn test_sections_with_same_headers_are_one_table() {
+        let problems = problems(&[("a@x", "one"), ("b@x", "two")]);
+        let output = write(b',', tabular::problem_groups(GroupBy::Maintainer, &group(problems, GroupBy::Maintainer)));
+        assert_eq!(output,
+                   "group,repo,name,effname,maintainer,kind,problem\n\
+                    a@x,arch,foo,foo,a@x,other,one\n\
+                    b@x,arch,foo,foo,b@x,other,two\n");
+    }
+}
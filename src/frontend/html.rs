@@ -0,0 +1,281 @@
+use std::io::Stdout;
+use std::io::Write;
+use std::ops::Deref;
+
+use librepology::v1::types::Package;
+use librepology::v1::types::Problem;
+use librepology::v1::types::Repo;
+use librepology::v1::project::Project;
+use failure::Fallible as Result;
+use url::Url;
+
+use crate::frontend::Frontend;
+use crate::frontend::escape_xml;
+use crate::frontend::tabular;
+use crate::frontend::tabular::Cell;
+use crate::frontend::tabular::Columns;
+use crate::frontend::tabular::DefaultColumns;
+use crate::frontend::tabular::Section;
+use crate::backend::Backend;
+use crate::compare::ComparePackage;
+use crate::outdated::OutdatedPackage;
+use crate::missing::MissingProject;
+use crate::repodiff::RepoDiffEntry;
+use crate::maintainer::MaintainerOverview;
+use crate::history::HistoryEntry;
+use crate::changes::ChangeSet;
+use crate::problemreport::GroupBy;
+use crate::problemreport::ProblemGroup;
+use crate::problemreport::ProblemSummary;
+
+const STYLE : &str = r#"
+body { font-family: sans-serif; color: #333; margin: 2em; }
+h1 { font-weight: normal; border-bottom: 1px solid #cbcbcb; padding-bottom: 0.3em; }
+h2 { font-weight: normal; margin-top: 1.5em; }
+table { border-collapse: collapse; border: 1px solid #cbcbcb; margin-bottom: 1em; }
+th, td { padding: 0.5em 1em; text-align: left; border-bottom: 1px solid #cbcbcb; }
+thead { background: #e0e0e0; vertical-align: bottom; }
+th { cursor: pointer; user-select: none; white-space: nowrap; }
+th.sorted-asc::after { content: " \25B2"; }
+th.sorted-desc::after { content: " \25BC"; }
+tbody tr:nth-child(2n) td { background: #f2f2f2; }
+tr.status-behind td { background: #f8d7da; }
+tr.status-ahead td { background: #fff3cd; }
+tr.status-equal td { background: #d4edda; }
+tr.status-missing td { background: #e2e3e5; color: #6c757d; }
+a { color: #0078e7; text-decoration: none; }
+a:hover { text-decoration: underline; }
+"#;
+
+const SCRIPT : &str = r#"
+document.querySelectorAll("table.sortable").forEach(function (table) {
+    var headers = table.querySelectorAll("th");
+    headers.forEach(function (th, column) {
+        th.addEventListener("click", function () {
+            var ascending = !th.classList.contains("sorted-asc");
+            headers.forEach(function (h) { h.classList.remove("sorted-asc", "sorted-desc"); });
+            th.classList.add(ascending ? "sorted-asc" : "sorted-desc");
+
+            var tbody = table.tBodies[0];
+            var rows = Array.prototype.slice.call(tbody.rows);
+            rows.sort(function (a, b) {
+                var x = a.cells[column].textContent;
+                var y = b.cells[column].textContent;
+                var order = x.localeCompare(y, undefined, { numeric: true });
+                return ascending ? order : -order;
+            });
+            rows.forEach(function (row) { tbody.appendChild(row); });
+        });
+    });
+});
+"#;
+
+/// A Frontend that prints a self-contained HTML page
+///
+/// The page has inline CSS and JavaScript and does not load anything from the network. Tables can
+/// be sorted by clicking on a column header, compare reports are coloured by status.
+pub struct HtmlFrontend {
+    stdout: Stdout,
+    repology_url: Url,
+    columns: Option<Columns>,
+}
+
+impl HtmlFrontend {
+    pub fn new(stdout: Stdout, repology_url: Url, columns: Option<Columns>) -> Self {
+        HtmlFrontend { stdout, repology_url, columns }
+    }
+
+    fn cell(&self, cell: &Cell) -> String {
+        match cell {
+            Cell::Text(text)             => escape_xml(text),
+            Cell::Link(text, url)        => link(text, url),
+            Cell::Project(text, project) => link(text, &tabular::project_url(&self.repology_url, project)),
+            Cell::List(cells)            => cells.iter().map(|c| self.cell(c)).collect::<Vec<_>>().join("<br>"),
+        }
+    }
+
+    fn write(&self, title: &str, sections: Vec<Section>) -> Result<()> {
+        let sections = tabular::select(sections, self.columns.as_ref(), DefaultColumns::All)?;
+        self.write_to(self.stdout.lock(), title, &sections)
+    }
+
+    fn write_to<W: Write>(&self, mut outlock: W, title: &str, sections: &[Section]) -> Result<()> {
+        writeln!(outlock, "<!DOCTYPE html>")?;
+        writeln!(outlock, "<html>")?;
+        writeln!(outlock, "<head>")?;
+        writeln!(outlock, "<meta charset=\"utf-8\">")?;
+        writeln!(outlock, "<title>repolocli: {}</title>", escape_xml(title))?;
+        writeln!(outlock, "<style>{}</style>", STYLE)?;
+        writeln!(outlock, "</head>")?;
+        writeln!(outlock, "<body>")?;
+        writeln!(outlock, "<h1>{}</h1>", escape_xml(title))?;
+
+        for section in tabular::printable(sections) {
+            if let Some(heading) = section.heading() {
+                writeln!(outlock, "<h2>{}</h2>", escape_xml(heading))?;
+            }
+
+            // Rows are coloured by the value of the status column, if there is one
+            let status_column = section.headers().iter().position(|h| h == "status");
+
+            writeln!(outlock, "<table class=\"sortable\">")?;
+            writeln!(outlock, "<thead>")?;
+            writeln!(outlock, "<tr>")?;
+            for header in section.headers() {
+                writeln!(outlock, "<th>{}</th>", escape_xml(header))?;
+            }
+            writeln!(outlock, "</tr>")?;
+            writeln!(outlock, "</thead>")?;
+            writeln!(outlock, "<tbody>")?;
+            for row in section.rows() {
+                match status_column.and_then(|i| row.get(i)) {
+                    Some(status) => writeln!(outlock, "<tr class=\"status-{}\">", escape_xml(&status.plain(" ")))?,
+                    None         => writeln!(outlock, "<tr>")?,
+                }
+                for cell in row {
+                    writeln!(outlock, "<td>{}</td>", self.cell(cell))?;
+                }
+                writeln!(outlock, "</tr>")?;
+            }
+            writeln!(outlock, "</tbody>")?;
+            writeln!(outlock, "</table>")?;
+        }
+
+        writeln!(outlock, "<script>{}</script>", SCRIPT)?;
+        writeln!(outlock, "</body>")?;
+        writeln!(outlock, "</html>")?;
+        Ok(())
+    }
+}
+
+fn link(text: &str, url: &str) -> String {
+    format!("<a href=\"{}\">{}</a>", escape_xml(url), escape_xml(text))
+}
+
+impl Frontend for HtmlFrontend {
+    fn list_packages(&self, packages: Vec<Package>) -> Result<()> {
+        self.write("Packages", tabular::packages(&packages))
+    }
+
+    fn list_projects(&self, projects: Vec<Project>) -> Result<()> {
+        self.write("Projects", tabular::projects(&projects))
+    }
+
+    fn project_summary(&self, projects: Vec<Project>) -> Result<()> {
+        self.write("Project summary", tabular::project_summary(&projects))
+    }
+
+    fn list_search_results(&self, projects: Vec<Project>) -> Result<()> {
+        self.write("Search results", tabular::search_results(&projects))
+    }
+
+    fn list_outdated(&self, packages: Vec<OutdatedPackage>) -> Result<()> {
+        self.write("Outdated packages", tabular::outdated(&packages))
+    }
+
+    fn list_missing(&self, projects: Vec<MissingProject>) -> Result<()> {
+        self.write("Missing projects", tabular::missing(&projects))
+    }
+
+    fn repo_diff(&self, repo_a: &Repo, repo_b: &Repo, entries: Vec<RepoDiffEntry>) -> Result<()> {
+        let title = format!("{} compared to {}", repo_a.deref(), repo_b.deref());
+        self.write(&title, tabular::repo_diff(repo_a, repo_b, &entries))
+    }
+
+    fn maintainer_overview(&self, overview: MaintainerOverview) -> Result<()> {
+        let title = format!("Maintainer {}", overview.maintainer());
+        self.write(&title, tabular::maintainer_overview(&overview))
+    }
+
+    fn list_history(&self, entries: Vec<HistoryEntry>) -> Result<()> {
+        self.write("History", tabular::history(&entries))
+    }
+
+    fn list_changes(&self, changes: ChangeSet) -> Result<()> {
+        self.write("Changes", tabular::changes(&changes))
+    }
+
+    fn list_problem_groups(&self, group_by: GroupBy, groups: Vec<ProblemGroup>) -> Result<()> {
+        let title = format!("Problems by {}", group_by);
+        self.write(&title, tabular::problem_groups(group_by, &groups))
+    }
+
+    fn problem_summary(&self, summary: ProblemSummary) -> Result<()> {
+        self.write("Problem summary", tabular::problem_summary(&summary))
+    }
+
+    fn list_problems(&self, problems: Vec<Problem>) -> Result<()> {
+        self.write("Problems", tabular::problems(&problems))
+    }
+
+    fn compare_packages(&self, packages: Vec<ComparePackage>, backend: &Backend, filter_repos: Vec<Repo>) -> Result<()> {
+        let rows = crate::compare::compare_rows(&packages, backend, &filter_repos)?;
+        self.write("Compare", tabular::compare(&rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(sections: Vec<Section>) -> String {
+        let frontend = HtmlFrontend::new(std::io::stdout(), Url::parse("https://repology.org/").unwrap(), None);
+        let mut buffer = vec![];
+        frontend.write_to(&mut buffer, "Test <view>", &sections).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    fn project(name: &str, packages: &str) -> Project {
+        Project::new(String::from(name), serde_json::from_str(packages).unwrap())
+    }
+
+    #[test]
+    fn test_escaping() {
+        let problems : Vec<Problem> = serde_json::from_str(r#"[
+            {"repo": "arch", "name": "a&b", "effname": "a&b", "maintainer": "me@x", "problem": "Link \"x\" isn't <ok>"}
+        ]"#).unwrap();
+        let output = write(tabular::problems(&problems));
+        assert!(output.contains("<title>repolocli: Test &lt;view&gt;</title>"));
+        assert!(output.contains("<td>a&amp;b</td>"));
+        assert!(output.contains("<td>Link &quot;x&quot; isn&#39;t &lt;ok&gt;</td>"));
+    }
+
+    #[test]
+    fn test_status_rows() {
+        let projects = vec![project("foo", r#"[
+            {"repo": "arch", "name": "foo", "version": "1.2", "status": "newest"},
+            {"repo": "debian_12", "name": "foo", "version": "1.0", "status": "outdated"}
+        ]"#)];
+        let output = write(tabular::projects(&projects));
+        assert!(output.contains("<tr class=\"status-newest\">"));
+        assert!(output.contains("<tr class=\"status-outdated\">"));
+    }
+
+    #[test]
+    fn test_rows_without_status() {
+        let problems : Vec<Problem> = serde_json::from_str(r#"[
+            {"repo": "arch", "name": "foo", "effname": "foo", "maintainer": "me@x", "problem": "Something"}
+        ]"#).unwrap();
+        let output = write(tabular::problems(&problems));
+        assert!(output.contains("<tbody>\n<tr>\n"));
+        assert!(!output.contains("<tr class="));
+    }
+
+    #[test]
+    fn test_links() {
+        let projects = vec![project("foo", r#"[
+            {"repo": "arch", "name": "foo", "version": "1.2", "status": "newest", "www": ["https://foo.org/?a=1&b=2"]}
+        ]"#)];
+        let output = write(tabular::projects(&projects));
+        assert!(output.contains("<td><a href=\"https://repology.org/project/foo/versions\">foo</a></td>"));
+        assert!(output.contains("<td><a href=\"https://repology.org/project/foo/versions\">newest</a></td>"));
+        assert!(output.contains("<td><a href=\"https://foo.org/?a=1&amp;b=2\">https://foo.org/?a=1&amp;b=2</a></td>"));
+    }
+
+    #[test]
+    fn test_not_found() {
+        let output = write(tabular::projects(&[Project::not_found(String::from("nope"))]));
+        assert!(output.contains("<h2>nope</h2>"));
+        assert!(output.contains("<td><a href=\"https://repology.org/project/nope/versions\">nope</a></td>\n<td></td>\n<td>&lt;not found&gt;</td>"));
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::BTreeMap;
+use std::io::Stdout;
+use std::io::Write;
+use std::ops::Deref;
+
+use librepology::v1::types::Package;
+use librepology::v1::types::Problem;
+use librepology::v1::types::Repo;
+use librepology::v1::project::Project;
+use failure::Fallible as Result;
+
+use crate::frontend::Frontend;
+use crate::frontend::escape_xml;
+use crate::backend::Backend;
+use crate::compare::CompareRow;
+use crate::compare::CompareStatus;
+use crate::compare::ComparePackage;
+use crate::outdated::OutdatedPackage;
+use crate::missing::MissingProject;
+use crate::repodiff::RepoDiffEntry;
+use crate::maintainer::MaintainerOverview;
+use crate::history::HistoryEntry;
+use crate::changes::ChangeSet;
+use crate::problemkind::ProblemKind;
+use crate::problemreport::GroupBy;
+use crate::problemreport::ProblemGroup;
+use crate::problemreport::ProblemSummary;
+
+/// A test case, failed if it has a failure
+struct TestCase {
+    name: String,
+    classname: String,
+    failure: Option<Failure>,
+}
+
+struct Failure {
+    kind: String,
+    message: String,
+    text: String,
+}
+
+/// A Frontend that prints JUnit XML, for showing results in the test view of a CI system
+///
+/// Each compared package in a repository is a test case, which fails if the package is behind or
+/// missing. Each problem is a failed test case. Test suites are the repositories.
+pub struct JunitFrontend(Stdout);
+
+impl JunitFrontend {
+    pub fn new(stdout: Stdout) -> Self {
+        JunitFrontend(stdout)
+    }
+
+    fn write(&self, name: &str, suites: BTreeMap<String, Vec<TestCase>>) -> Result<()> {
+        write_suites(self.0.lock(), name, &suites)
+    }
+
+    fn unsupported(&self, view: &str) -> Result<()> {
+        Err(format_err!("Cannot print {} as JUnit XML, only compare results and problems", view))
+    }
+}
+
+/// Write the test suites as a JUnit XML document
+fn write_suites<W: Write>(mut outlock: W, name: &str, suites: &BTreeMap<String, Vec<TestCase>>) -> Result<()> {
+    let count = |cases: &[TestCase]| (cases.len(), cases.iter().filter(|c| c.failure.is_some()).count());
+    let (tests, failures) = suites
+        .values()
+        .map(|cases| count(cases))
+        .fold((0, 0), |(t, f), (tests, failures)| (t + tests, f + failures));
+
+    writeln!(outlock, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(outlock, "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\">", escape_xml(name), tests, failures)?;
+
+    for (suite, cases) in suites.iter() {
+        let (tests, failures) = count(cases);
+        writeln!(outlock, "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\">",
+                 escape_xml(suite), tests, failures)?;
+
+        for case in cases {
+            let attributes = format!("name=\"{}\" classname=\"{}\"", escape_xml(&case.name), escape_xml(&case.classname));
+            match case.failure.as_ref() {
+                None => writeln!(outlock, "    <testcase {}/>", attributes)?,
+                Some(failure) => {
+                    writeln!(outlock, "    <testcase {}>", attributes)?;
+                    writeln!(outlock, "      <failure type=\"{}\" message=\"{}\">{}</failure>",
+                             escape_xml(&failure.kind), escape_xml(&failure.message), escape_xml(&failure.text))?;
+                    writeln!(outlock, "    </testcase>")?;
+                },
+            }
+        }
+
+        writeln!(outlock, "  </testsuite>")?;
+    }
+
+    writeln!(outlock, "</testsuites>")?;
+    Ok(())
+}
+
+/// One test suite per repository, with a failed test case per problem
+fn problem_suites<'a, I>(problems: I) -> BTreeMap<String, Vec<TestCase>>
+    where I: IntoIterator<Item = &'a Problem>
+{
+    let mut suites : BTreeMap<String, Vec<TestCase>> = BTreeMap::new();
+    for problem in problems {
+        let kind = ProblemKind::of(problem).to_string();
+        let case = TestCase {
+            name: problem.name().deref().clone(),
+            classname: format!("{}.{}", problem.repo().deref(), kind),
+            failure: Some(Failure {
+                message: problem.problem_description().clone(),
+                text: format!("Maintainer: {}\nProject: {}", problem.maintainer().deref(), problem.effname().deref()),
+                kind,
+            }),
+        };
+
+        suites.entry(problem.repo().deref().clone()).or_default().push(case);
+    }
+    suites
+}
+
+/// One test suite per repository, with a test case per compared package which fails if the
+/// package is behind or missing
+fn compare_suites(rows: &[CompareRow]) -> BTreeMap<String, Vec<TestCase>> {
+    let mut suites : BTreeMap<String, Vec<TestCase>> = BTreeMap::new();
+    for row in rows {
+        let repo = row.repo().deref().clone();
+        let version = row.version().map(|v| v.deref().clone()).unwrap_or_default();
+        let failure = match row.status() {
+            CompareStatus::Behind => Some(format!("{} has {}, local version is {}", repo, version, row.local_version())),
+            CompareStatus::Missing => Some(format!("{} is not packaged in {}", row.package_name(), repo)),
+            CompareStatus::Equal | CompareStatus::Ahead => None,
+        };
+
+        let case = TestCase {
+            name: row.package_name().clone(),
+            classname: format!("{}.{}", repo, row.package_name()),
+            failure: failure.map(|message| Failure {
+                kind: row.status().to_string(),
+                text: message.clone(),
+                message,
+            }),
+        };
+
+        suites.entry(repo).or_default().push(case);
+    }
+    suites
+}
+
+impl Frontend for JunitFrontend {
+    fn list_packages(&self, _packages: Vec<Package>) -> Result<()> {
+        self.unsupported("packages")
+    }
+
+    fn list_projects(&self, _projects: Vec<Project>) -> Result<()> {
+        self.unsupported("projects")
+    }
+
+    fn project_summary(&self, _projects: Vec<Project>) -> Result<()> {
+        self.unsupported("a project summary")
+    }
+
+    fn list_search_results(&self, _projects: Vec<Project>) -> Result<()> {
+        self.unsupported("search results")
+    }
+
+    fn list_outdated(&self, _packages: Vec<OutdatedPackage>) -> Result<()> {
+        self.unsupported("outdated packages")
+    }
+
+    fn list_missing(&self, _projects: Vec<MissingProject>) -> Result<()> {
+        self.unsupported("missing projects")
+    }
+
+    fn repo_diff(&self, _repo_a: &Repo, _repo_b: &Repo, _entries: Vec<RepoDiffEntry>) -> Result<()> {
+        self.unsupported("a repository diff")
+    }
+
+    fn maintainer_overview(&self, _overview: MaintainerOverview) -> Result<()> {
+        self.unsupported("a maintainer overview")
+    }
+
+    fn list_history(&self, _entries: Vec<HistoryEntry>) -> Result<()> {
+        self.unsupported("a history")
+    }
+
+    fn list_changes(&self, _changes: ChangeSet) -> Result<()> {
+        self.unsupported("changes")
+    }
+
+    fn list_problem_groups(&self, _group_by: GroupBy, groups: Vec<ProblemGroup>) -> Result<()> {
+        self.write("repolocli problems", problem_suites(groups.iter().flat_map(|g| g.problems())))
+    }
+
+    fn problem_summary(&self, _summary: ProblemSummary) -> Result<()> {
+        self.unsupported("a problem summary")
+    }
+
+    fn list_problems(&self, problems: Vec<Problem>) -> Result<()> {
+        self.write("repolocli problems", problem_suites(problems.iter()))
+    }
+
+    fn compare_packages(&self, packages: Vec<ComparePackage>, backend: &Backend, filter_repos: Vec<Repo>) -> Result<()> {
+        let rows = crate::compare::compare_rows(&packages, backend, &filter_repos)?;
+        self.write("repolocli compare", compare_suites(&rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::compare::package_rows;
+
+    fn write(name: &str, suites: BTreeMap<String, Vec<TestCase>>) -> String {
+        let mut buffer = vec![];
+        write_suites(&mut buffer, name, &suites).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    fn compare(local_version: &str, upstream: &str) -> String {
+        let package : ComparePackage = serde_json::from_str(
+            &format!(r#"{{"name": "foo", "version": "{}", "comment": ""}}"#, local_version)
+        ).unwrap();
+        let upstream : Vec<Package> = serde_json::from_str(upstream).unwrap();
+        let repos = vec![Repo::new(String::from("arch")), Repo::new(String::from("debian_12"))];
+        write("repolocli compare", compare_suites(&package_rows(&package, &upstream, &repos)))
+    }
+
+    #[test]
+    fn test_compare_behind_and_missing_fail() {
+        let output = compare("1.0", r#"[{"repo": "arch", "name": "foo", "version": "1.2"}]"#);
+        assert!(output.contains(r#"<testsuites name="repolocli compare" tests="2" failures="2" errors="0">"#));
+        assert!(output.contains(r#"<failure type="behind" message="arch has 1.2, local version is 1.0">"#));
+        assert!(output.contains(r#"<failure type="missing" message="foo is not packaged in debian_12">"#));
+    }
+
+    #[test]
+    fn test_compare_equal_and_ahead_pass() {
+        let output = compare("1.2", r#"[{"repo": "arch", "name": "foo", "version": "1.2"},
+                                         {"repo": "debian_12", "name": "foo", "version": "1.0"}]"#);
+        assert!(output.contains(r#"<testsuites name="repolocli compare" tests="2" failures="0" errors="0">"#));
+        assert!(output.contains(r#"<testcase name="foo" classname="arch.foo"/>"#));
+        assert!(output.contains(r#"<testcase name="foo" classname="debian_12.foo"/>"#));
+        assert!(!output.contains("<failure"));
+    }
+
+    #[test]
+    fn test_problems_fail() {
+        let problems : Vec<Problem> = serde_json::from_str(r#"[
+            {"repo": "arch", "name": "foo", "effname": "foo", "maintainer": "me@x", "problem": "Homepage link \"http://foo.org\" is dead"},
+            {"repo": "debian_12", "name": "bar", "effname": "bar", "maintainer": "me@x", "problem": "Something else"}
+        ]"#).unwrap();
+        let output = write("repolocli problems", problem_suites(problems.iter()));
+        assert!(output.contains(r#"<testsuites name="repolocli problems" tests="2" failures="2" errors="0">"#));
+        assert!(output.contains(r#"<testsuite name="arch" tests="1" failures="1" errors="0" skipped="0">"#));
+        assert!(output.contains(r#"<testcase name="foo" classname="arch.homepage-dead">"#));
+        assert!(output.contains(r#"<failure type="other" message="Something else">Maintainer: me@x
+Project: bar</failure>"#));
+    }
+
+    #[test]
+    fn test_escaping() {
+        let problems : Vec<Problem> = serde_json::from_str(r#"[
+            {"repo": "arch", "name": "a&b", "effname": "a&b", "maintainer": "<me@x>", "problem": "Link \"x\" isn't <ok>"}
+        ]"#).unwrap();
+        let output = write("repolocli problems", problem_suites(problems.iter()));
+        assert!(output.contains(r#"<testcase name="a&amp;b" classname="arch.other">"#));
+        assert!(output.contains(r#"message="Link &quot;x&quot; isn&#39;t &lt;ok&gt;""#));
+        assert!(output.contains("Maintainer: &lt;me@x&gt;\nProject: a&amp;b</failure>"));
+    }
+}
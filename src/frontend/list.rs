@@ -2,101 +2,346 @@ use std::io::Stdout;
 use std::io::Write;
 use std::ops::Deref;
 
-use librepology::v1::types::Name;
 use librepology::v1::types::Package;
 use librepology::v1::types::Problem;
 use librepology::v1::types::Repo;
+use librepology::v1::types::Status;
+use librepology::v1::types::Version;
+use librepology::v1::project::Project;
 use failure::Fallible as Result;
+use chrono::SecondsFormat;
 use failure::Error;
 
 use crate::frontend::Frontend;
+use crate::frontend::tabular;
+use crate::frontend::tabular::Columns;
+use crate::frontend::tabular::DefaultColumns;
+use crate::frontend::tabular::Section;
 use crate::backend::Backend;
 use crate::compare::ComparePackage;
-use librepology::v1::api::Api;
-
-pub struct ListFrontend(Stdout);
+use crate::outdated::OutdatedPackage;
+use crate::missing::MissingProject;
+use crate::repodiff::RepoDiffEntry;
+use crate::maintainer::MaintainerOverview;
+use crate::history::HistoryEntry;
+use crate::changes::ChangeKind;
+use crate::changes::ChangeSet;
+use crate::problemreport::GroupBy;
+use crate::problemreport::ProblemGroup;
+use crate::problemreport::ProblemSummary;
+use crate::changes::ProblemChangeKind;
 
 /// A Frontend that prints the data in a human-readable way but without ASCII-art.
 ///
 /// It seperates the values with dashes ("-") for a slightly better reading experience.
+pub struct ListFrontend {
+    stdout: Stdout,
+    columns: Option<Columns>,
+}
+
 impl ListFrontend {
-    pub fn new(stdout: Stdout) -> Self {
-        ListFrontend(stdout)
+    pub fn new(stdout: Stdout, columns: Option<Columns>) -> Self {
+        ListFrontend { stdout, columns }
+    }
+
+    /// The rows of the first section of a view, with the selected columns
+    fn lines(&self, sections: Vec<Section>) -> Result<Vec<String>> {
+        Ok(tabular::select(sections, self.columns.as_ref(), DefaultColumns::Compact)?
+            .first()
+            .map(lines)
+            .unwrap_or_default())
     }
 }
 
+/// The rows of a section, each value padded to the longest value of its column
+fn lines(section: &Section) -> Vec<String> {
+    let rows = section.rows()
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.plain(", ")).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let widths = (0..section.headers().len())
+        .map(|i| rows.iter().map(|row| row[i].chars().count()).max().unwrap_or(0))
+        .collect::<Vec<_>>();
+
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(widths.iter())
+                .map(|(value, width)| format!("{:width$}", value, width = width))
+                .collect::<Vec<_>>()
+                .join(" - ")
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
 impl Frontend for ListFrontend {
     fn list_packages(&self, packages: Vec<Package>) -> Result<()> {
-        let mut outlock = self.0.lock();
-
-        packages.iter().fold(Ok(()), |accu, package| {
-            accu.and_then(|_| {
-                let status= if let Some(stat) = package.status() {
-                    stat.deref().to_string()
-                } else {
-                    String::from("No status")
-                }; // not optimal, but works for now.
-
-                let url= if let Some(url) = package.www() {
-                    if let Some(url) = url.first() {
-                        url.deref().to_string()
-                    } else {
-                        String::from("")
-                    }
-                } else {
-                    String::from("")
-                }; // not optimal, but works for now
-
-                writeln!(outlock,
-                         "{name:10} - {version:8} - {repo:15} - {status:5} - {www}",
-                         name = package.any_name().map(Name::deref).map(String::deref).unwrap_or_else(|| "<unknown>"),
-                         version = package.version().deref(),
-                         repo = package.repo().deref(),
-                         status = status,
-                         www = url).map(|_| ()).map_err(Error::from)
-            })
-        })
+        let mut outlock = self.stdout.lock();
+        self.lines(tabular::packages(&packages))?.iter().try_for_each(|line| writeln!(outlock, "{}", line))?;
+        Ok(())
     }
 
-    fn list_problems(&self, problems: Vec<Problem>) -> Result<()> {
-        let mut outlock = self.0.lock();
-
-        problems.iter().fold(Ok(()), |accu, problem| {
-            accu.and_then(|_| {
-                writeln!(outlock,
-                         "{repo:10} - {name:10} - {effname:10} - {maintainer:15} - {desc}",
-                         repo = problem.repo().deref(),
-                         name = problem.name().deref(),
-                         effname = problem.effname().deref(),
-                         maintainer = problem.maintainer().deref(),
-                         desc = problem.problem_description())
-                    .map(|_| ())
+    fn list_projects(&self, projects: Vec<Project>) -> Result<()> {
+        let mut outlock = self.stdout.lock();
+
+        projects.iter().try_for_each(|project| {
+            if !project.is_found() {
+                writeln!(outlock, "{}: not found", project.name()).map_err(Error::from)
+            } else {
+                writeln!(outlock, "{}:", project.name())?;
+                self.lines(tabular::projects(std::slice::from_ref(project)))?
+                    .iter()
+                    .try_for_each(|line| writeln!(outlock, "{}", line))
                     .map_err(Error::from)
-            })
+            }
         })
     }
 
-    fn compare_packages(&self, packages: Vec<ComparePackage>, backend: &Backend, filter_repos: Vec<Repo>) -> Result<()> {
-        let mut output = self.0.lock();
+    fn project_summary(&self, projects: Vec<Project>) -> Result<()> {
+        let mut outlock = self.stdout.lock();
+
+        for (i, project) in projects.iter().enumerate() {
+            if i != 0 {
+                writeln!(outlock)?;
+            }
+
+            writeln!(outlock, "{:12} {}", "Project:", project.name())?;
+            if !project.is_found() {
+                writeln!(outlock, "{:12} not found", "")?;
+                continue;
+            }
 
-        for package in packages {
-            backend
-                .project(package.name().deref())?
+            let newest = project.newest_version().map(|v| v.deref().clone()).unwrap_or_else(|| String::from("-"));
+            let devel  = project.newest_devel_version().map(|v| v.deref().clone()).unwrap_or_else(|| String::from("-"));
+            let status = project.repo_count_by_status()
                 .into_iter()
-                .filter(|p| filter_repos.contains(p.repo()))
-                .map(|upstream_package| {
-                    writeln!(output,
-                             "{our_package_name} - {our_package_version} - {up_repo_name} - {up_package_version}",
-                             our_package_name    = package.name().deref(),
-                             our_package_version = package.version().deref(),
-                             up_repo_name        = upstream_package.repo().deref(),
-                             up_package_version  = upstream_package.version().deref()
-                    ).map_err(Error::from)
-                })
-                .collect::<Result<Vec<()>>>()?;
+                .map(|(status, count)| format!("{}: {}", status, count))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let maintainers = project.maintainers()
+                .into_iter()
+                .map(|m| m.deref().clone())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let licenses = project.license_consensus()
+                .map(|ls| ls.iter().map(|l| l.deref().clone()).collect::<Vec<_>>().join(", "))
+                .unwrap_or_else(|| String::from("<no consensus>"));
+
+            writeln!(outlock, "{:12} {}", "Newest:", newest)?;
+            writeln!(outlock, "{:12} {}", "Devel:", devel)?;
+            writeln!(outlock, "{:12} {}", "Repos:", project.repos().len())?;
+            writeln!(outlock, "{:12} {}", "Status:", status)?;
+            writeln!(outlock, "{:12} {}", "Maintainers:", maintainers)?;
+            writeln!(outlock, "{:12} {}", "Licenses:", licenses)?;
         }
 
         Ok(())
     }
-}
 
+    fn list_search_results(&self, projects: Vec<Project>) -> Result<()> {
+        let mut outlock = self.stdout.lock();
+
+        projects.iter().try_for_each(|project| {
+            writeln!(outlock,
+                     "{name:25} - {newest:10} - {repos} repos",
+                     name = project.name(),
+                     newest = project.newest_version().map(|v| v.deref().as_str()).unwrap_or("-"),
+                     repos = project.repos().len())
+                .map_err(Error::from)
+        })
+    }
+
+    fn list_outdated(&self, packages: Vec<OutdatedPackage>) -> Result<()> {
+        let mut outlock = self.stdout.lock();
+
+        packages.iter().try_for_each(|package| {
+            writeln!(outlock,
+                     "{project:15} - {name:15} - {version:8} - {newest:8} - {repos}",
+                     project = package.project(),
+                     name = package.name().map(|n| n.deref().as_str()).unwrap_or("<unknown>"),
+                     version = package.version().deref(),
+                     newest = package.newest_version().map(|v| v.deref().as_str()).unwrap_or("-"),
+                     repos = package.newest_repos().iter().map(|r| r.deref().as_str()).collect::<Vec<_>>().join(", "))
+                .map_err(Error::from)
+        })
+    }
+
+    fn list_missing(&self, projects: Vec<MissingProject>) -> Result<()> {
+        let mut outlock = self.stdout.lock();
+
+        projects.iter().try_for_each(|project| {
+            writeln!(outlock,
+                     "{project:25} - {newest:10} - {repos}",
+                     project = project.project(),
+                     newest = project.newest_version().map(|v| v.deref().as_str()).unwrap_or("-"),
+                     repos = project.reference_repos().iter().map(|r| r.deref().as_str()).collect::<Vec<_>>().join(", "))
+                .map_err(Error::from)
+        })
+    }
+
+    fn repo_diff(&self, _repo_a: &Repo, _repo_b: &Repo, entries: Vec<RepoDiffEntry>) -> Result<()> {
+        let mut outlock = self.stdout.lock();
+
+        entries.iter().try_for_each(|entry| {
+            writeln!(outlock,
+                     "{status:7} - {project:25} - {a:10} - {b:10}",
+                     status = entry.status().to_string(),
+                     project = entry.project(),
+                     a = entry.version_a().map(|v| v.deref().as_str()).unwrap_or("-"),
+                     b = entry.version_b().map(|v| v.deref().as_str()).unwrap_or("-"))
+                .map_err(Error::from)
+        })
+    }
+
+    fn maintainer_overview(&self, overview: MaintainerOverview) -> Result<()> {
+        let mut outlock = self.stdout.lock();
+
+        let status = overview.packages_by_status()
+            .iter()
+            .map(|(status, count)| format!("{}: {}", status, count))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        writeln!(outlock, "{:12} {}", "Maintainer:", overview.maintainer())?;
+        writeln!(outlock, "{:12} {} ({})", "Packages:", overview.packages().len(), status)?;
+        writeln!(outlock, "{:12} {}", "Problems:", overview.problems().len())?;
+
+        writeln!(outlock)?;
+        overview.packages().iter().try_for_each(|package| {
+            writeln!(outlock,
+                     "{project:15} - {repo:15} - {name:15} - {version:8} - {status:8} - {newest}",
+                     project = package.project(),
+                     repo = package.repo().deref(),
+                     name = package.name().map(|n| n.deref().as_str()).unwrap_or("<unknown>"),
+                     version = package.version().deref(),
+                     status = package.status().map(|s| s.to_string()).unwrap_or_else(|| String::from("No status")),
+                     newest = package.newest_version().map(|v| v.deref().as_str()).unwrap_or("-"))
+                .map_err(Error::from)
+        })?;
+
+        if !overview.problems().is_empty() {
+            writeln!(outlock)?;
+            self.lines(tabular::problems(overview.problems()))?
+                .iter()
+                .try_for_each(|line| writeln!(outlock, "{}", line))?;
+        }
+
+        Ok(())
+    }
+
+    fn list_history(&self, entries: Vec<HistoryEntry>) -> Result<()> {
+        let mut outlock = self.stdout.lock();
+
+        entries.iter().try_for_each(|entry| {
+            writeln!(outlock,
+                     "{timestamp} - {repo:15} - {name:15} - {old} -> {new}",
+                     timestamp = entry.timestamp().to_rfc3339_opts(SecondsFormat::Secs, true),
+                     repo = entry.repo().deref(),
+                     name = entry.name().map(|n| n.deref().as_str()).unwrap_or("<unknown>"),
+                     old = entry.old_version().map(|v| v.deref().as_str()).unwrap_or("-"),
+                     new = entry.new_version().map(|v| v.deref().as_str()).unwrap_or("-"))
+                .map_err(Error::from)
+        })
+    }
+
+    fn list_changes(&self, changes: ChangeSet) -> Result<()> {
+        let mut outlock = self.stdout.lock();
+
+        // "version (status)", or "-" if the package did not exist
+        let state = |version: Option<&Version>, status: Option<&Status>| -> String {
+            match (version, status) {
+                (Some(v), Some(s)) => format!("{} ({})", v.deref(), s),
+                (Some(v), None)    => v.deref().clone(),
+                (None, _)          => String::from("-"),
+            }
+        };
+
+        for change in changes.packages() {
+            let sign = match change.change() {
+                ChangeKind::Appeared    => "+",
+                ChangeKind::Disappeared => "-",
+                ChangeKind::Changed     => "~",
+            };
+
+            writeln!(outlock,
+                     "{sign} {project:15} - {repo:15} - {name:15} - {old} -> {new}",
+                     sign = sign,
+                     project = change.project().map(String::as_str).unwrap_or("-"),
+                     repo = change.repo().deref(),
+                     name = change.name().map(|n| n.deref().as_str()).unwrap_or("<unknown>"),
+                     old = state(change.old_version(), change.old_status()),
+                     new = state(change.new_version(), change.new_status()))?;
+        }
+
+        let problems = changes.problems().iter().map(|c| c.problem().clone()).collect::<Vec<_>>();
+        for (change, line) in changes.problems().iter().zip(self.lines(tabular::problems(&problems))?) {
+            let sign = match change.change() {
+                ProblemChangeKind::New   => "+",
+                ProblemChangeKind::Fixed => "-",
+            };
+
+            writeln!(outlock, "{} {}", sign, line)?;
+        }
+
+        Ok(())
+    }
+
+    fn list_problem_groups(&self, group_by: GroupBy, groups: Vec<ProblemGroup>) -> Result<()> {
+        let mut outlock = self.stdout.lock();
+
+        for (i, group) in groups.iter().enumerate() {
+            if i != 0 {
+                writeln!(outlock)?;
+            }
+
+            let kinds = group.count_by_kind()
+                .iter()
+                .map(|(kind, count)| format!("{}: {}", kind, count))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            writeln!(outlock, "{} {} - {} ({})", group_by, group.key(), group.count_text(), kinds)?;
+            for line in self.lines(tabular::problems(group.problems()))? {
+                writeln!(outlock, "    {}", line)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn problem_summary(&self, summary: ProblemSummary) -> Result<()> {
+        let mut outlock = self.stdout.lock();
+
+        writeln!(outlock, "{:24} {}", "Total:", summary.total())?;
+
+        writeln!(outlock)?;
+        writeln!(outlock, "By kind:")?;
+        for (kind, count) in summary.by_kind() {
+            writeln!(outlock, "{:24} {}", kind.to_string(), count)?;
+        }
+
+        writeln!(outlock)?;
+        writeln!(outlock, "By maintainer:")?;
+        for (maintainer, count) in summary.by_maintainer() {
+            writeln!(outlock, "{:24} {}", maintainer, count)?;
+        }
+
+        Ok(())
+    }
+
+    fn list_problems(&self, problems: Vec<Problem>) -> Result<()> {
+        let mut outlock = self.stdout.lock();
+        self.lines(tabular::problems(&problems))?.iter().try_for_each(|line| writeln!(outlock, "{}", line))?;
+        Ok(())
+    }
+
+    fn compare_packages(&self, packages: Vec<ComparePackage>, backend: &Backend, filter_repos: Vec<Repo>) -> Result<()> {
+        let rows = crate::compare::compare_rows(&packages, backend, &filter_repos)?;
+        let mut outlock = self.stdout.lock();
+        self.lines(tabular::compare(&rows))?.iter().try_for_each(|line| writeln!(outlock, "{}", line))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+use std::io::Stdout;
+use std::io::Write;
+
+use librepology::v1::types::Package;
+use librepology::v1::types::Problem;
+use librepology::v1::types::Repo;
+use librepology::v1::project::Project;
+use failure::Fallible as Result;
+use url::Url;
+
+use crate::frontend::Frontend;
+use crate::frontend::tabular;
+use crate::frontend::tabular::Cell;
+use crate::frontend::tabular::Columns;
+use crate::frontend::tabular::DefaultColumns;
+use crate::frontend::tabular::Section;
+use crate::backend::Backend;
+use crate::compare::ComparePackage;
+use crate::outdated::OutdatedPackage;
+use crate::missing::MissingProject;
+use crate::repodiff::RepoDiffEntry;
+use crate::maintainer::MaintainerOverview;
+use crate::history::HistoryEntry;
+use crate::changes::ChangeSet;
+use crate::problemreport::GroupBy;
+use crate::problemreport::ProblemGroup;
+use crate::problemreport::ProblemSummary;
+
+/// A Frontend that prints GitHub-flavoured Markdown tables
+///
+/// Status and project names link to the project page on repology, homepages and downloads link
+/// to themselves. Views with several tables print a heading above each table.
+pub struct MarkdownFrontend {
+    stdout: Stdout,
+    repology_url: Url,
+    columns: Option<Columns>,
+}
+
+impl MarkdownFrontend {
+    pub fn new(stdout: Stdout, repology_url: Url, columns: Option<Columns>) -> Self {
+        MarkdownFrontend { stdout, repology_url, columns }
+    }
+
+    fn cell(&self, cell: &Cell) -> String {
+        match cell {
+            Cell::Text(text)             => escape(text),
+            Cell::Link(text, url)        => link(text, url),
+            Cell::Project(text, project) => link(text, &tabular::project_url(&self.repology_url, project)),
+            Cell::List(cells)            => cells.iter().map(|c| self.cell(c)).collect::<Vec<_>>().join(", "),
+        }
+    }
+
+    fn write(&self, sections: Vec<Section>) -> Result<()> {
+        let sections = tabular::select(sections, self.columns.as_ref(), DefaultColumns::All)?;
+        let mut outlock = self.stdout.lock();
+
+        for (i, section) in tabular::printable(&sections).into_iter().enumerate() {
+            if i != 0 {
+                writeln!(outlock)?;
+            }
+
+            if let Some(heading) = section.heading() {
+                writeln!(outlock, "## {}", escape(heading))?;
+                writeln!(outlock)?;
+            }
+
+            let headers = section.headers().iter().map(|h| escape(h)).collect::<Vec<_>>();
+            writeln!(outlock, "{}", row(&headers))?;
+            writeln!(outlock, "{}", row(&vec![String::from("---"); headers.len()]))?;
+
+            for cells in section.rows() {
+                let cells = cells.iter().map(|c| self.cell(c)).collect::<Vec<_>>();
+                writeln!(outlock, "{}", row(&cells))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn row(cells: &[String]) -> String {
+    format!("| {} |", cells.join(" | "))
+}
+
+/// Escape a text so that it is shown literally in a table cell
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '~' | '#' => {
+                escaped.push('\\');
+                escaped.push(c);
+            },
+            '\r' => {},
+            '\n' => escaped.push_str("<br>"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A link, the destination is enclosed in angle brackets so that parentheses need no escaping
+fn link(text: &str, url: &str) -> String {
+    let url = url
+        .replace('|', "%7C")
+        .replace('<', "%3C")
+        .replace('>', "%3E")
+        .replace(' ', "%20");
+    format!("[{}](<{}>)", escape(text), url)
+}
+
+impl Frontend for MarkdownFrontend {
+    fn list_packages(&self, packages: Vec<Package>) -> Result<()> {
+        self.write(tabular::packages(&packages))
+    }
+
+    fn list_projects(&self, projects: Vec<Project>) -> Result<()> {
+        self.write(tabular::projects(&projects))
+    }
+
+    fn project_summary(&self, projects: Vec<Project>) -> Result<()> {
+        self.write(tabular::project_summary(&projects))
+    }
+
+    fn list_search_results(&self, projects: Vec<Project>) -> Result<()> {
+        self.write(tabular::search_results(&projects))
+    }
+
+    fn list_outdated(&self, packages: Vec<OutdatedPackage>) -> Result<()> {
+        self.write(tabular::outdated(&packages))
+    }
+
+    fn list_missing(&self, projects: Vec<MissingProject>) -> Result<()> {
+        self.write(tabular::missing(&projects))
+    }
+
+    fn repo_diff(&self, repo_a: &Repo, repo_b: &Repo, entries: Vec<RepoDiffEntry>) -> Result<()> {
+        self.write(tabular::repo_diff(repo_a, repo_b, &entries))
+    }
+
+    fn maintainer_overview(&self, overview: MaintainerOverview) -> Result<()> {
+        self.write(tabular::maintainer_overview(&overview))
+    }
+
+    fn list_history(&self, entries: Vec<HistoryEntry>) -> Result<()> {
+        self.write(tabular::history(&entries))
+    }
+
+    fn list_changes(&self, changes: ChangeSet) -> Result<()> {
+        self.write(tabular::changes(&changes))
+    }
+
+    fn list_problem_groups(&self, group_by: GroupBy, groups: Vec<ProblemGroup>) -> Result<()> {
+        self.write(tabular::problem_groups(group_by, &groups))
+    }
+
+    fn problem_summary(&self, summary: ProblemSummary) -> Result<()> {
+        self.write(tabular::problem_summary(&summary))
+    }
+
+    fn list_problems(&self, problems: Vec<Problem>) -> Result<()> {
+        self.write(tabular::problems(&problems))
+    }
+
+    fn compare_packages(&self, packages: Vec<ComparePackage>, backend: &Backend, filter_repos: Vec<Repo>) -> Result<()> {
+        let rows = crate::compare::compare_rows(&packages, backend, &filter_repos)?;
+        self.write(tabular::compare(&rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_plain_text() {
+        assert_eq!(escape("foo 1.2.3"), "foo 1.2.3");
+    }
+
+    #[test]
+    fn test_escape_markup() {
+        assert_eq!(escape("a|b"), "a\\|b");
+        assert_eq!(escape("*foo_bar*"), "\\*foo\\_bar\\*");
+        assert_eq!(escape("<script>"), "\\<script\\>");
+        assert_eq!(escape("[x](y)"), "\\[x\\](y)");
+        assert_eq!(escape("`code` #1 ~a~ \\"), "\\`code\\` \\#1 \\~a\\~ \\\\");
+    }
+
+    #[test]
+    fn test_escape_newlines() {
+        assert_eq!(escape("foo\r\nbar\nbaz"), "foo<br>bar<br>baz");
+    }
+
+    #[test]
+    fn test_link() {
+        assert_eq!(link("foo", "https://foo.org/"), "[foo](<https://foo.org/>)");
+        assert_eq!(link("foo_bar", "https://foo.org/a_(b)"), "[foo\\_bar](<https://foo.org/a_(b)>)");
+    }
+
+    #[test]
+    fn test_link_escapes_url() {
+        assert_eq!(link("x", "https://foo.org/a b|c<d>"), "[x](<https://foo.org/a%20b%7Cc%3Cd%3E>)");
+    }
+}
@@ -2,43 +2,214 @@ use clap::ArgMatches;
 use failure::Fallible as Result;
 
 use librepology::v1::types::*;
+use librepology::v1::project::Project;
 
 use crate::config::Configuration;
+use crate::frontend::tabular::Columns;
+use crate::frontend::tabular::RowKind;
+use crate::output::Query;
 use crate::frontend::list::ListFrontend;
 use crate::frontend::json::JsonFrontend;
 use crate::frontend::table::TableFrontend;
 use crate::compare::ComparePackage;
+use crate::outdated::OutdatedPackage;
+use crate::missing::MissingProject;
+use crate::repodiff::RepoDiffEntry;
+use crate::maintainer::MaintainerOverview;
+use crate::history::HistoryEntry;
+use crate::changes::ChangeSet;
+use crate::problemreport::GroupBy;
+use crate::problemreport::ProblemGroup;
+use crate::problemreport::ProblemSummary;
 use crate::backend::Backend;
 
 /// A Frontend represents a way to show the data to the user
 pub trait Frontend {
     fn list_packages(&self, packages: Vec<Package>) -> Result<()>;
+    fn list_projects(&self, projects: Vec<Project>) -> Result<()>;
+    fn project_summary(&self, projects: Vec<Project>) -> Result<()>;
+    fn list_search_results(&self, projects: Vec<Project>) -> Result<()>;
+    fn list_outdated(&self, packages: Vec<OutdatedPackage>) -> Result<()>;
+    fn list_missing(&self, projects: Vec<MissingProject>) -> Result<()>;
+    fn repo_diff(&self, repo_a: &Repo, repo_b: &Repo, entries: Vec<RepoDiffEntry>) -> Result<()>;
+    fn maintainer_overview(&self, overview: MaintainerOverview) -> Result<()>;
+    fn list_history(&self, entries: Vec<HistoryEntry>) -> Result<()>;
+    fn list_changes(&self, changes: ChangeSet) -> Result<()>;
+    fn list_problem_groups(&self, group_by: GroupBy, groups: Vec<ProblemGroup>) -> Result<()>;
+    fn problem_summary(&self, summary: ProblemSummary) -> Result<()>;
     fn list_problems(&self, problems: Vec<Problem>) -> Result<()>;
     fn compare_packages(&self, packages: Vec<ComparePackage>, backend: &Backend, filter_repos: Vec<Repo>) -> Result<()>;
+
+    /// List outdated packages page by page, as they are fetched
+    ///
+    /// Frontends which print rows as they arrive override this, the others list all pages at once.
+    fn list_outdated_pages(&self, pages: Pages<'_, OutdatedPackage>) -> Result<()> {
+        self.list_outdated(collect_pages(pages)?)
+    }
+
+    /// List missing projects page by page, as they are fetched
+    ///
+    /// Frontends which list all pages at once list projects which are packaged in more of the
+    /// reference repositories first.
+    fn list_missing_pages(&self, pages: Pages<'_, MissingProject>) -> Result<()> {
+        let mut projects = collect_pages(pages)?;
+        crate::missing::sort_by_references(&mut projects);
+        self.list_missing(projects)
+    }
+}
+
+/// Rows of a view, page by page as they are fetched from repology
+pub type Pages<'a, T> = Box<dyn Iterator<Item = Result<Vec<T>>> + 'a>;
+
+fn collect_pages<T>(pages: Pages<'_, T>) -> Result<Vec<T>> {
+    pages
+        .collect::<Result<Vec<_>>>()
+        .map(|pages| pages.into_iter().flatten().collect())
 }
 
 pub mod list;
 pub mod json;
+pub mod ndjson;
+pub mod toml;
 pub mod table;
+pub mod markdown;
+pub mod html;
+pub mod junit;
+pub mod openmetrics;
+mod tabular;
+mod serialized;
+
+#[cfg(feature = "output_csv")]
+pub mod csv;
+
+#[cfg(feature = "output_template")]
+pub mod template;
+
+#[cfg(feature = "output_yaml")]
+pub mod yaml;
+
+/// Escape a text for HTML and XML, in element content as well as in quoted attributes
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&'  => escaped.push_str("&amp;"),
+            '<'  => escaped.push_str("&lt;"),
+            '>'  => escaped.push_str("&gt;"),
+            '"'  => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c    => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The columns selected for the subcommand, with `--columns` or in the configuration
+fn columns(app: &ArgMatches, config: &Configuration) -> Result<Option<Columns>> {
+    let (kind, mtch, default) = match app.subcommand() {
+        ("project", Some(mtch))  => (RowKind::Package, mtch, config.columns().project()),
+        ("problems", Some(mtch)) => (RowKind::Problem, mtch, config.columns().problems()),
+        ("compare", Some(mtch))  => (RowKind::Compare, mtch, config.columns().compare()),
+        _                        => return Ok(None),
+    };
+
+    mtch.value_of("columns")
+        .or_else(|| default.map(String::as_str))
+        .map(|s| Columns::parse(kind, s))
+        .transpose()
+}
 
 /// Helper function for building a new Frontend object based on the commandline parameters
-pub fn new_frontend(app: &ArgMatches, _config: &Configuration) -> Result<Box<dyn Frontend>> {
+pub fn new_frontend(app: &ArgMatches, config: &Configuration) -> Result<Box<dyn Frontend>> {
+    let columns = columns(app, config)?;
+    trace!("Columns: {:?}", columns);
+
     match app.value_of("output") {
         None | Some("lines") => {
             debug!("No output specified, using default");
-            Ok(Box::new(ListFrontend::new(::std::io::stdout())))
+            Ok(Box::new(ListFrontend::new(::std::io::stdout(), columns)))
         },
 
         Some("json") => {
             debug!("Using JSON Frontend");
-            Ok(Box::new(JsonFrontend::new(::std::io::stdout())))
+            Ok(Box::new(JsonFrontend::new(::std::io::stdout(), Query::from_matches(app))))
+        },
+
+        Some("ndjson") => {
+            debug!("Using NDJSON Frontend");
+            Ok(Box::new(crate::frontend::ndjson::NdjsonFrontend::new(::std::io::stdout())))
+        },
+
+        #[cfg(feature = "output_yaml")]
+        Some("yaml") => {
+            debug!("Using YAML Frontend");
+            Ok(Box::new(crate::frontend::yaml::YamlFrontend::new(::std::io::stdout(), Query::from_matches(app))))
+        },
+
+        #[cfg(not(feature = "output_yaml"))]
+        Some("yaml") => Err(format_err!("repolocli was built without YAML support")),
+
+        Some("toml") => {
+            debug!("Using TOML Frontend");
+            Ok(Box::new(crate::frontend::toml::TomlFrontend::new(::std::io::stdout(), Query::from_matches(app))))
         },
 
         Some("table") => {
             debug!("Using table Frontend");
-            Ok(Box::new(TableFrontend::new(::std::io::stdout())))
+            Ok(Box::new(TableFrontend::new(::std::io::stdout(), columns)))
+        },
+
+        Some("markdown") => {
+            debug!("Using Markdown Frontend");
+            let url = config.repology_url().clone();
+            Ok(Box::new(crate::frontend::markdown::MarkdownFrontend::new(::std::io::stdout(), url, columns)))
+        },
+
+        Some("html") => {
+            debug!("Using HTML Frontend");
+            let url = config.repology_url().clone();
+            Ok(Box::new(crate::frontend::html::HtmlFrontend::new(::std::io::stdout(), url, columns)))
+        },
+
+        Some("junit") => {
+            debug!("Using JUnit Frontend");
+            Ok(Box::new(crate::frontend::junit::JunitFrontend::new(::std::io::stdout())))
         },
 
+        Some("openmetrics") => {
+            debug!("Using OpenMetrics Frontend");
+            Ok(Box::new(crate::frontend::openmetrics::OpenMetricsFrontend::new(::std::io::stdout())))
+        },
+
+        #[cfg(feature = "output_csv")]
+        Some("csv") => {
+            debug!("Using CSV Frontend");
+            let sep = config.csv().list_separator().to_string();
+            Ok(Box::new(crate::frontend::csv::CsvFrontend::new(::std::io::stdout(), b',', sep, columns)))
+        },
+
+        #[cfg(feature = "output_csv")]
+        Some("tsv") => {
+            debug!("Using TSV Frontend");
+            let sep = config.csv().list_separator().to_string();
+            Ok(Box::new(crate::frontend::csv::CsvFrontend::new(::std::io::stdout(), b'\t', sep, columns)))
+        },
+
+        #[cfg(not(feature = "output_csv"))]
+        Some("csv") | Some("tsv") => Err(format_err!("repolocli was built without CSV support")),
+
+        #[cfg(feature = "output_template")]
+        Some("template") => {
+            debug!("Using template Frontend");
+            let path = app.value_of("template")
+                .map(std::path::Path::new)
+                .ok_or_else(|| format_err!("--output template needs --template FILE"))?;
+            Ok(Box::new(crate::frontend::template::TemplateFrontend::new(::std::io::stdout(), path)?))
+        },
+
+        #[cfg(not(feature = "output_template"))]
+        Some("template") => Err(format_err!("repolocli was built without template support")),
+
         Some(other) => Err(format_err!("Unknown Frontend '{}'", other)),
     }
 
@@ -0,0 +1,169 @@
+use std::io::Stdout;
+use std::io::Write;
+
+use librepology::v1::types::Package;
+use librepology::v1::types::Problem;
+use librepology::v1::types::Repo;
+use librepology::v1::project::Project;
+use failure::Fallible as Result;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::frontend::Frontend;
+use crate::frontend::Pages;
+use crate::frontend::serialized::SerializingFrontend;
+use crate::backend::Backend;
+use crate::compare::ComparePackage;
+use crate::outdated::OutdatedPackage;
+use crate::missing::MissingProject;
+use crate::repodiff::RepoDiffEntry;
+use crate::maintainer::MaintainerOverview;
+use crate::history::HistoryEntry;
+use crate::changes::ChangeSet;
+use crate::problemreport::GroupBy;
+use crate::problemreport::ProblemGroup;
+use crate::problemreport::ProblemSummary;
+use crate::output::Kind;
+
+/// A Frontend that prints newline-delimited JSON
+///
+/// Every package, problem or compare row is printed as a compact JSON object on its own line,
+/// without the envelope of the JSON frontend. Compare rows are printed as soon as the package is
+/// looked up. Views which are not a list of rows, like summaries, are printed as a single line.
+/// Projects which were not found are printed as `{"project": ..., "found": false}`. Outdated packages
+/// and missing projects are printed page by page as they are fetched, so missing projects are not
+/// sorted by the number of reference repositories.
+pub struct NdjsonFrontend(Stdout);
+
+impl NdjsonFrontend {
+    pub fn new(stdout: Stdout) -> Self {
+        NdjsonFrontend(stdout)
+    }
+
+    fn write_rows<T, I>(&self, rows: I) -> Result<()>
+        where T: Serialize,
+              I: IntoIterator<Item = T>
+    {
+        for row in rows {
+            let mut outlock = self.0.lock();
+            serde_json::to_writer(&mut outlock, &row)?;
+            writeln!(outlock)?;
+        }
+        Ok(())
+    }
+
+    /// The data model of the JSON frontend, for views without rows of their own
+    fn document(&self) -> Document<'_> {
+        Document(self)
+    }
+}
+
+/// Prints the JSON data model line by line, elements of a list on a line each
+struct Document<'a>(&'a NdjsonFrontend);
+
+impl<'a> SerializingFrontend for Document<'a> {
+    fn serialize<T: Serialize>(&self, _kind: Kind, data: &T) -> Result<()> {
+        match serde_json::to_value(data)? {
+            Value::Array(rows) => self.0.write_rows(rows),
+            other              => self.0.write_rows(Some(other)),
+        }
+    }
+}
+
+impl Frontend for NdjsonFrontend {
+    fn list_packages(&self, packages: Vec<Package>) -> Result<()> {
+        self.write_rows(packages)
+    }
+
+    fn list_projects(&self, projects: Vec<Project>) -> Result<()> {
+        #[derive(Serialize)]
+        struct ProjectPackage<'a> {
+            project: &'a String,
+            #[serde(flatten)]
+            package: &'a Package,
+        }
+
+        #[derive(Serialize)]
+        struct NotFound<'a> {
+            project: &'a String,
+            found: bool,
+        }
+
+        for project in projects.iter() {
+            if project.is_found() {
+                self.write_rows(project.packages().iter().map(|package| ProjectPackage { project: project.name(), package }))?;
+            } else {
+                self.write_rows(Some(NotFound { project: project.name(), found: false }))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn project_summary(&self, projects: Vec<Project>) -> Result<()> {
+        self.document().project_summary(projects)
+    }
+
+    fn list_search_results(&self, projects: Vec<Project>) -> Result<()> {
+        self.document().list_search_results(projects)
+    }
+
+    fn list_outdated(&self, packages: Vec<OutdatedPackage>) -> Result<()> {
+        self.write_rows(packages)
+    }
+
+    fn list_missing(&self, projects: Vec<MissingProject>) -> Result<()> {
+        self.write_rows(projects)
+    }
+
+    fn list_outdated_pages(&self, mut pages: Pages<'_, OutdatedPackage>) -> Result<()> {
+        pages.try_for_each(|page| self.write_rows(page?))
+    }
+
+    fn list_missing_pages(&self, mut pages: Pages<'_, MissingProject>) -> Result<()> {
+        pages.try_for_each(|page| self.write_rows(page?))
+    }
+
+    fn repo_diff(&self, repo_a: &Repo, repo_b: &Repo, entries: Vec<RepoDiffEntry>) -> Result<()> {
+        #[derive(Serialize)]
+        struct RepoDiffRow<'a> {
+            repo_a: &'a Repo,
+            repo_b: &'a Repo,
+            #[serde(flatten)]
+            entry: RepoDiffEntry,
+        }
+
+        self.write_rows(entries.into_iter().map(|entry| RepoDiffRow { repo_a, repo_b, entry }))
+    }
+
+    fn maintainer_overview(&self, overview: MaintainerOverview) -> Result<()> {
+        self.document().maintainer_overview(overview)
+    }
+
+    fn list_history(&self, entries: Vec<HistoryEntry>) -> Result<()> {
+        self.write_rows(entries)
+    }
+
+    fn list_changes(&self, changes: ChangeSet) -> Result<()> {
+        self.document().list_changes(changes)
+    }
+
+    fn list_problem_groups(&self, _group_by: GroupBy, groups: Vec<ProblemGroup>) -> Result<()> {
+        self.write_rows(groups)
+    }
+
+    fn problem_summary(&self, summary: ProblemSummary) -> Result<()> {
+        self.document().problem_summary(summary)
+    }
+
+    fn list_problems(&self, problems: Vec<Problem>) -> Result<()> {
+        self.write_rows(problems)
+    }
+
+    fn compare_packages(&self, packages: Vec<ComparePackage>, backend: &Backend, filter_repos: Vec<Repo>) -> Result<()> {
+        for package in packages.iter() {
+            let result = crate::compare::compare_result(package, backend, &filter_repos)?;
+            self.write_rows(Some(result))?;
+        }
+        Ok(())
+    }
+}
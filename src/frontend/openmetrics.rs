@@ -0,0 +1,287 @@
+use std::collections::BTreeMap;
+use std::io::Stdout;
+use std::io::Write;
+use std::ops::Deref;
+
+use librepology::v1::types::Package;
+use librepology::v1::types::Problem;
+use librepology::v1::types::Repo;
+use librepology::v1::types::Status;
+use librepology::v1::project::Project;
+use failure::Fallible as Result;
+
+use crate::frontend::Frontend;
+use crate::backend::Backend;
+use crate::compare::ComparePackage;
+use crate::outdated::OutdatedPackage;
+use crate::missing::MissingProject;
+use crate::repodiff::RepoDiffEntry;
+use crate::maintainer::MaintainerOverview;
+use crate::history::HistoryEntry;
+use crate::changes::ChangeSet;
+use crate::problemkind::ProblemKind;
+use crate::problemreport::GroupBy;
+use crate::problemreport::ProblemGroup;
+use crate::problemreport::ProblemSummary;
+
+/// A metric family: all samples of one metric, by their labels
+struct Family {
+    name: &'static str,
+    help: &'static str,
+    samples: BTreeMap<Vec<(&'static str, String)>, usize>,
+}
+
+impl Family {
+    fn new(name: &'static str, help: &'static str) -> Self {
+        Family { name, help, samples: BTreeMap::new() }
+    }
+
+    fn set(&mut self, labels: Vec<(&'static str, String)>, value: usize) {
+        self.samples.insert(labels, value);
+    }
+
+    fn add(&mut self, labels: Vec<(&'static str, String)>, value: usize) {
+        *self.samples.entry(labels).or_insert(0) += value;
+    }
+}
+
+/// A Frontend that prints gauges in the OpenMetrics text format
+///
+/// The output can be scraped from a file by the textfile collector of node_exporter. Only the
+/// views which have something to count are supported: packages of projects by status, compare
+/// results by status and problems by kind.
+pub struct OpenMetricsFrontend(Stdout);
+
+impl OpenMetricsFrontend {
+    pub fn new(stdout: Stdout) -> Self {
+        OpenMetricsFrontend(stdout)
+    }
+
+    fn write(&self, families: Vec<Family>) -> Result<()> {
+        write_families(self.0.lock(), families)
+    }
+
+    fn unsupported(&self, view: &str) -> Result<()> {
+        Err(format_err!("Cannot print {} as OpenMetrics, only packages, compare results and problems", view))
+    }
+}
+
+/// Write the metric families in the OpenMetrics text format, terminated by `# EOF`
+fn write_families<W: Write>(mut outlock: W, families: Vec<Family>) -> Result<()> {
+    for family in families {
+        writeln!(outlock, "# HELP {} {}", family.name, family.help)?;
+        writeln!(outlock, "# TYPE {} gauge", family.name)?;
+
+        for (labels, value) in family.samples {
+            let labels = labels
+                .iter()
+                .map(|(key, value)| format!("{}=\"{}\"", key, escape(value)))
+                .collect::<Vec<_>>()
+                .join(",");
+
+            writeln!(outlock, "{}{{{}}} {}", family.name, labels, value)?;
+        }
+    }
+
+    writeln!(outlock, "# EOF")?;
+    Ok(())
+}
+
+/// Escape a label value
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn status_label(status: Option<&Status>) -> String {
+    status.map(|s| s.to_string()).unwrap_or_else(|| String::from("unknown"))
+}
+
+fn packages_family() -> Family {
+    Family::new("repolocli_packages", "Number of packages by repository and status")
+}
+
+/// Whether the projects were found and the number of their packages by repository and status
+fn project_families(projects: &[Project]) -> Vec<Family> {
+    let mut found = Family::new("repolocli_project_found", "1 if repology knows the project, 0 if not");
+    let mut family = packages_family();
+    for project in projects.iter() {
+        found.set(vec![("project", project.name().clone())], project.is_found() as usize);
+
+        for package in project.packages() {
+            family.add(vec![
+                ("project", project.name().clone()),
+                ("repo", package.repo().deref().clone()),
+                ("status", status_label(package.status())),
+            ], 1);
+        }
+    }
+    vec![found, family]
+}
+
+fn problems_family<'a, I>(problems: I) -> Family
+    where I: IntoIterator<Item = &'a Problem>
+{
+    let mut family = Family::new("repolocli_problems", "Number of problems by repository, maintainer and kind");
+    for problem in problems {
+        family.add(vec![
+            ("repo", problem.repo().deref().clone()),
+            ("maintainer", problem.maintainer().deref().clone()),
+            ("kind", ProblemKind::of(problem).to_string()),
+        ], 1);
+    }
+    family
+}
+
+impl Frontend for OpenMetricsFrontend {
+    fn list_packages(&self, _packages: Vec<Package>) -> Result<()> {
+        // Every sample of repolocli_packages has a project label, which is not known here
+        Err(format_err!("Cannot print packages of an unknown project as OpenMetrics, pass the name of the project: repolocli --stdin project NAME"))
+    }
+
+    fn list_projects(&self, projects: Vec<Project>) -> Result<()> {
+        self.write(project_families(&projects))
+    }
+
+    fn project_summary(&self, _projects: Vec<Project>) -> Result<()> {
+        self.unsupported("a project summary")
+    }
+
+    fn list_search_results(&self, _projects: Vec<Project>) -> Result<()> {
+        self.unsupported("search results")
+    }
+
+    fn list_outdated(&self, _packages: Vec<OutdatedPackage>) -> Result<()> {
+        self.unsupported("outdated packages")
+    }
+
+    fn list_missing(&self, _projects: Vec<MissingProject>) -> Result<()> {
+        self.unsupported("missing projects")
+    }
+
+    fn repo_diff(&self, _repo_a: &Repo, _repo_b: &Repo, _entries: Vec<RepoDiffEntry>) -> Result<()> {
+        self.unsupported("a repository diff")
+    }
+
+    fn maintainer_overview(&self, overview: MaintainerOverview) -> Result<()> {
+        let mut packages = packages_family();
+        for package in overview.packages() {
+            packages.add(vec![
+                ("project", package.project().clone()),
+                ("repo", package.repo().deref().clone()),
+                ("status", status_label(package.status())),
+            ], 1);
+        }
+
+        self.write(vec![packages, problems_family(overview.problems())])
+    }
+
+    fn list_history(&self, _entries: Vec<HistoryEntry>) -> Result<()> {
+        self.unsupported("a history")
+    }
+
+    fn list_changes(&self, _changes: ChangeSet) -> Result<()> {
+        self.unsupported("changes")
+    }
+
+    fn list_problem_groups(&self, _group_by: GroupBy, groups: Vec<ProblemGroup>) -> Result<()> {
+        self.write(vec![problems_family(groups.iter().flat_map(|g| g.problems()))])
+    }
+
+    fn problem_summary(&self, summary: ProblemSummary) -> Result<()> {
+        let mut by_kind = Family::new("repolocli_problems_by_kind", "Number of problems by kind");
+        for (kind, count) in summary.by_kind() {
+            by_kind.set(vec![("kind", kind.to_string())], *count);
+        }
+
+        let mut by_maintainer = Family::new("repolocli_problems_by_maintainer", "Number of problems by maintainer");
+        for (maintainer, count) in summary.by_maintainer() {
+            by_maintainer.set(vec![("maintainer", maintainer.clone())], *count);
+        }
+
+        self.write(vec![by_kind, by_maintainer])
+    }
+
+    fn list_problems(&self, problems: Vec<Problem>) -> Result<()> {
+        self.write(vec![problems_family(problems.iter())])
+    }
+
+    fn compare_packages(&self, packages: Vec<ComparePackage>, backend: &Backend, filter_repos: Vec<Repo>) -> Result<()> {
+        let rows = crate::compare::compare_rows(&packages, backend, &filter_repos)?;
+
+        let mut status = Family::new("repolocli_compare_status",
+                                     "1 for the status of the local version compared to the version in a repository");
+        let mut count = Family::new("repolocli_compare_packages", "Number of local packages by repository and status");
+        for row in rows.iter() {
+            status.set(vec![
+                ("project", row.package_name().clone()),
+                ("repo", row.repo().deref().clone()),
+                ("status", row.status().to_string()),
+            ], 1);
+
+            count.add(vec![
+                ("repo", row.repo().deref().clone()),
+                ("status", row.status().to_string()),
+            ], 1);
+        }
+
+        self.write(vec![status, count])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(families: Vec<Family>) -> String {
+        let mut buffer = vec![];
+        write_families(&mut buffer, families).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(escape("a\\b"), "a\\\\b");
+        assert_eq!(escape("say \"hi\""), "say \\\"hi\\\"");
+        assert_eq!(escape("two\nlines"), "two\\nlines");
+    }
+
+    #[test]
+    fn test_escaped_labels() {
+        let mut family = Family::new("repolocli_test", "A test");
+        family.set(vec![("maintainer", String::from("\"me\"\\x\n"))], 1);
+        assert_eq!(write(vec![family]),
+                   "# HELP repolocli_test A test\n\
+                    # TYPE repolocli_test gauge\n\
+                    repolocli_test{maintainer=\"\\\"me\\\"\\\\x\\n\"} 1\n\
+                    # EOF\n");
+    }
+
+    #[test]
+    fn test_eof() {
+        assert_eq!(write(vec![]), "# EOF\n");
+
+        let output = write(vec![packages_family(), Family::new("repolocli_other", "Other")]);
+        assert!(output.ends_with("# TYPE repolocli_other gauge\n# EOF\n"));
+        assert_eq!(output.matches("# EOF").count(), 1);
+    }
+
+    #[test]
+    fn test_projects() {
+        let packages = serde_json::from_str(r#"[
+            {"repo": "arch", "name": "foo", "version": "1.2", "status": "newest"},
+            {"repo": "debian_12", "name": "foo", "version": "1.0", "status": "outdated"}
+        ]"#).unwrap();
+        let projects = vec![Project::new(String::from("foo"), packages), Project::not_found(String::from("nope"))];
+        let output = write(project_families(&projects));
+
+        assert!(output.contains("repolocli_project_found{project=\"foo\"} 1\n"));
+        assert!(output.contains("repolocli_project_found{project=\"nope\"} 0\n"));
+        assert!(output.contains("repolocli_packages{project=\"foo\",repo=\"arch\",status=\"newest\"} 1\n"));
+        assert!(output.contains("repolocli_packages{project=\"foo\",repo=\"debian_12\",status=\"outdated\"} 1\n"));
+        assert!(!output.contains("repolocli_packages{project=\"nope\""));
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+use std::ops::Deref;
+
+use librepology::v1::types::Package;
+use librepology::v1::types::Problem;
+use librepology::v1::types::Repo;
+use librepology::v1::types::Status;
+use librepology::v1::project::Project;
+use failure::Fallible as Result;
+use serde::Serialize;
+
+use crate::frontend::Frontend;
+use crate::backend::Backend;
+use crate::compare::ComparePackage;
+use crate::outdated::OutdatedPackage;
+use crate::missing::MissingProject;
+use crate::repodiff::RepoDiffEntry;
+use crate::maintainer::MaintainerOverview;
+use crate::history::HistoryEntry;
+use crate::changes::ChangeSet;
+use crate::problemreport::GroupBy;
+use crate::problemreport::ProblemGroup;
+use crate::problemreport::ProblemSummary;
+use crate::output::Kind;
+
+/// A Frontend that prints the data of the views with serde
+///
+/// All views are implemented on top of `serialize()`, so that frontends like JSON and templates
+/// work on the same data model.
+pub trait SerializingFrontend {
+    fn serialize<T: Serialize>(&self, kind: Kind, data: &T) -> Result<()>;
+}
+
+/// The packages of projects by the name of the project
+///
+/// Projects which were not found are listed with `null`.
+pub fn projects_by_name(projects: Vec<Project>) -> BTreeMap<String, Option<Vec<Package>>> {
+    projects
+        .into_iter()
+        .map(|project| {
+            let name = project.name().clone();
+            let packages = Some(project).filter(Project::is_found).map(Project::into_packages);
+            (name, packages)
+        })
+        .collect()
+}
+
+impl<F: SerializingFrontend> Frontend for F {
+    fn list_packages(&self, packages: Vec<Package>) -> Result<()> {
+        self.serialize(Kind::Packages, &packages)
+    }
+
+    fn list_projects(&self, projects: Vec<Project>) -> Result<()> {
+        self.serialize(Kind::Projects, &projects_by_name(projects))
+    }
+
+    fn project_summary(&self, projects: Vec<Project>) -> Result<()> {
+        #[derive(Serialize)]
+        struct ProjectSummary {
+            newest_version: Option<String>,
+            newest_devel_version: Option<String>,
+            repos: usize,
+            repos_by_status: BTreeMap<Status, usize>,
+            maintainers: Vec<String>,
+            licenses: Option<Vec<String>>,
+        }
+
+        // Projects which were not found are listed with `null`
+        let output = projects
+            .iter()
+            .map(|project| {
+                let summary = Some(project).filter(|p| p.is_found()).map(|project| ProjectSummary {
+                    newest_version: project.newest_version().map(|v| v.deref().clone()),
+                    newest_devel_version: project.newest_devel_version().map(|v| v.deref().clone()),
+                    repos: project.repos().len(),
+                    repos_by_status: project.repo_count_by_status(),
+                    maintainers: project.maintainers().into_iter().map(|m| m.deref().clone()).collect(),
+                    licenses: project.license_consensus().map(|ls| ls.iter().map(|l| l.deref().clone()).collect()),
+                });
+
+                (project.name().clone(), summary)
+            })
+            .collect::<BTreeMap<String, Option<ProjectSummary>>>();
+
+        self.serialize(Kind::ProjectSummary, &output)
+    }
+
+    fn list_search_results(&self, projects: Vec<Project>) -> Result<()> {
+        #[derive(Serialize)]
+        struct SearchResult {
+            name: String,
+            newest_version: Option<String>,
+            repos: usize,
+        }
+
+        let output = projects
+            .iter()
+            .map(|project| SearchResult {
+                name: project.name().clone(),
+                newest_version: project.newest_version().map(|v| v.deref().clone()),
+                repos: project.repos().len(),
+            })
+            .collect::<Vec<_>>();
+
+        self.serialize(Kind::SearchResults, &output)
+    }
+
+    fn list_outdated(&self, packages: Vec<OutdatedPackage>) -> Result<()> {
+        self.serialize(Kind::Outdated, &packages)
+    }
+
+    fn list_missing(&self, projects: Vec<MissingProject>) -> Result<()> {
+        self.serialize(Kind::Missing, &projects)
+    }
+
+    fn repo_diff(&self, repo_a: &Repo, repo_b: &Repo, entries: Vec<RepoDiffEntry>) -> Result<()> {
+        #[derive(Serialize)]
+        struct RepoDiff<'a> {
+            repo_a: &'a Repo,
+            repo_b: &'a Repo,
+            projects: Vec<RepoDiffEntry>,
+        }
+
+        self.serialize(Kind::RepoDiff, &RepoDiff { repo_a, repo_b, projects: entries })
+    }
+
+    fn maintainer_overview(&self, overview: MaintainerOverview) -> Result<()> {
+        self.serialize(Kind::MaintainerOverview, &overview)
+    }
+
+    fn list_history(&self, entries: Vec<HistoryEntry>) -> Result<()> {
+        self.serialize(Kind::History, &entries)
+    }
+
+    fn list_changes(&self, changes: ChangeSet) -> Result<()> {
+        self.serialize(Kind::Changes, &changes)
+    }
+
+    fn list_problem_groups(&self, group_by: GroupBy, groups: Vec<ProblemGroup>) -> Result<()> {
+        #[derive(Serialize)]
+        struct ProblemGroups {
+            group_by: GroupBy,
+            groups: Vec<ProblemGroup>,
+        }
+
+        self.serialize(Kind::ProblemGroups, &ProblemGroups { group_by, groups })
+    }
+
+    fn problem_summary(&self, summary: ProblemSummary) -> Result<()> {
+        self.serialize(Kind::ProblemSummary, &summary)
+    }
+
+    fn list_problems(&self, problems: Vec<Problem>) -> Result<()> {
+        self.serialize(Kind::Problems, &problems)
+    }
+
+    fn compare_packages(&self, packages: Vec<ComparePackage>, backend: &Backend, filter_repos: Vec<Repo>) -> Result<()> {
+        let results = crate::compare::compare_results(&packages, backend, &filter_repos)?;
+        self.serialize(Kind::Compare, &results)
+    }
+}
@@ -1,25 +1,89 @@
 use std::io::Stdout;
-use std::ops::Deref;
+use std::io::Write;
 
-use librepology::v1::types::Name;
 use librepology::v1::types::Package;
 use librepology::v1::types::Problem;
 use librepology::v1::types::Repo;
+use librepology::v1::project::Project;
 use failure::Fallible as Result;
 use prettytable::format;
+use prettytable::Cell;
+use prettytable::Row;
 use prettytable::Table;
+use terminal_size::terminal_size;
+use terminal_size::Width;
+use unicode_width::UnicodeWidthChar;
+use unicode_width::UnicodeWidthStr;
 
 use crate::frontend::Frontend;
+use crate::frontend::tabular;
+use crate::frontend::tabular::Columns;
+use crate::frontend::tabular::DefaultColumns;
+use crate::frontend::tabular::Section;
 use crate::backend::Backend;
 use crate::compare::ComparePackage;
-use librepology::v1::api::Api;
+use crate::outdated::OutdatedPackage;
+use crate::missing::MissingProject;
+use crate::repodiff::RepoDiffEntry;
+use crate::maintainer::MaintainerOverview;
+use crate::history::HistoryEntry;
+use crate::changes::ChangeSet;
+use crate::problemreport::GroupBy;
+use crate::problemreport::ProblemGroup;
+use crate::problemreport::ProblemSummary;
+
+/// Columns are not narrowed below this width to fit a table into the terminal
+const MIN_COLUMN_WIDTH : usize = 8;
 
 /// A Frontend that formats the output in a nice ASCII-art table
-pub struct TableFrontend(Stdout);
+///
+/// If the output is a terminal, tables are fit to its width: the widest columns are narrowed,
+/// text in them is wrapped at spaces and words which do not fit, like URLs, are truncated.
+pub struct TableFrontend {
+    stdout: Stdout,
+    columns: Option<Columns>,
+    width: Option<usize>,
+}
 
 impl TableFrontend {
-    pub fn new(stdout: Stdout) -> Self {
-        TableFrontend(stdout)
+    pub fn new(stdout: Stdout, columns: Option<Columns>) -> Self {
+        let width = if atty::is(atty::Stream::Stdout) {
+            terminal_size().map(|(Width(w), _)| w as usize)
+        } else {
+            None
+        };
+
+        debug!("Fitting tables to width: {:?}", width);
+        TableFrontend { stdout, columns, width }
+    }
+
+    /// The titles and rows of a section
+    fn section_rows(section: &Section) -> (Vec<String>, Vec<Vec<String>>) {
+        let titles = section.headers().iter().map(|h| title(h)).collect();
+        let rows = section.rows()
+            .iter()
+            .map(|row| row.iter().map(|c| c.plain("\n")).collect())
+            .collect();
+        (titles, rows)
+    }
+
+    fn print_section(&self, section: &Section) -> Result<()> {
+        let (titles, rows) = Self::section_rows(section);
+        self.print_table(titles, rows)
+    }
+
+    /// Print the sections of a view with the selected columns, each below its heading
+    fn print(&self, sections: Vec<Section>) -> Result<()> {
+        let sections = tabular::select(sections, self.columns.as_ref(), DefaultColumns::Compact)?;
+
+        for section in tabular::printable(&sections) {
+            if let Some(heading) = section.heading() {
+                writeln!(self.stdout.lock(), "{}:", heading)?;
+            }
+            self.print_section(section)?;
+        }
+
+        Ok(())
     }
 
     fn mktable(&self) -> Table {
@@ -34,79 +98,168 @@ impl TableFrontend {
             .padding(1, 1)
             .build();
         table.set_format(format);
-        table.set_titles(row!["Name", "Version", "Repo", "Status", "URL"]);
         table
     }
 
-    fn print(&self, table: Table) -> Result<()> {
-        let mut outlock = self.0.lock();
+    /// Print a table with the titles and rows, fit to the width of the terminal
+    fn print_table<T: AsRef<str>>(&self, titles: Vec<T>, mut rows: Vec<Vec<String>>) -> Result<()> {
+        let titles = titles.iter().map(|t| t.as_ref().to_string()).collect::<Vec<_>>();
+        if let Some(width) = self.width {
+            fit(&titles, &mut rows, width);
+        }
+
+        let mut table = self.mktable();
+        table.set_titles(titles.iter().map(|t| Cell::new(t)).collect());
+        rows.iter().for_each(|row| {
+            table.add_row(row.iter().map(|c| Cell::new(c)).collect::<Row>());
+        });
+
+        let mut outlock = self.stdout.lock();
         table.print(&mut outlock)?;
         Ok(())
     }
 }
 
+/// The title of a column, "package_name" becomes "Package name"
+fn title(header: &str) -> String {
+    let mut chars = header.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect::<String>().replace('_', " "),
+        None    => String::new(),
+    }
+}
+
+fn text_width(text: &str) -> usize {
+    text.lines().map(UnicodeWidthStr::width).max().unwrap_or(0)
+}
+
+/// Narrow the widest columns until the table fits into `width`, then wrap the cells
+fn fit(titles: &[String], rows: &mut [Vec<String>], width: usize) {
+    let mut widths = titles.iter().map(|t| text_width(t)).collect::<Vec<_>>();
+    rows.iter().for_each(|row| {
+        row.iter().zip(widths.iter_mut()).for_each(|(cell, w)| *w = (*w).max(text_width(cell)));
+    });
+
+    // Every column has a padding of one on both sides and a separator
+    let overhead = 3 * widths.len() + 1;
+    let minimum = |i: usize| MIN_COLUMN_WIDTH.max(text_width(&titles[i]));
+    while widths.iter().sum::<usize>() + overhead > width {
+        let widest = widths
+            .iter()
+            .enumerate()
+            .filter(|&(i, w)| *w > minimum(i))
+            .max_by_key(|&(_, w)| *w)
+            .map(|(i, _)| i);
+
+        match widest {
+            Some(i) => widths[i] -= 1,
+            None    => break,
+        }
+    }
+
+    rows.iter_mut().for_each(|row| {
+        row.iter_mut().zip(widths.iter()).for_each(|(cell, w)| {
+            if text_width(cell) > *w {
+                *cell = wrap(cell, *w);
+            }
+        });
+    });
+}
+
+/// Wrap text at spaces to lines of at most `width`, truncating words which are longer
+fn wrap(text: &str, width: usize) -> String {
+    let mut lines = vec![];
+    for line in text.lines() {
+        let mut current = String::new();
+        for word in line.split_whitespace() {
+            let word = truncate(word, width);
+            if !current.is_empty() && current.width() + 1 + word.width() > width {
+                lines.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(&word);
+        }
+        lines.push(current);
+    }
+    lines.join("\n")
+}
+
+fn truncate(word: &str, width: usize) -> String {
+    if word.width() <= width {
+        return word.to_string();
+    }
+
+    let mut truncated = String::new();
+    let mut used = 0;
+    for c in word.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w + 1 > width {
+            break;
+        }
+        used += w;
+        truncated.push(c);
+    }
+    truncated.push('…');
+    truncated
+}
+
 impl Frontend for TableFrontend {
     fn list_packages(&self, packages: Vec<Package>) -> Result<()> {
-        let mut table = self.mktable();
-        packages.iter().for_each(|package| {
-            let status = if let Some(stat) = package.status() {
-                format!("{}", stat)
-            } else {
-                String::from("No status")
-            }; // not optimal, but works for now.
-
-            let url = if let Some(url) = package.www() {
-                if let Some(url) = url.first() {
-                    format!("{}", url.deref())
-                } else {
-                    String::from("")
-                }
-            } else {
-                String::from("")
-            }; // not optimal, but works for now
-
-            let name = package.any_name()
-                .map(Name::deref)
-                .map(String::clone)
-                .unwrap_or_else(|| String::from("<unknown>"));
-
-            table.add_row(row![name, package.version(), package.repo(), status, url]);
-        });
-        self.print(table)
+        self.print(tabular::packages(&packages))
+    }
+
+    fn list_projects(&self, projects: Vec<Project>) -> Result<()> {
+        self.print(tabular::projects(&projects))
+    }
+
+    fn project_summary(&self, projects: Vec<Project>) -> Result<()> {
+        self.print(tabular::project_summary(&projects))
+    }
+
+    fn list_search_results(&self, projects: Vec<Project>) -> Result<()> {
+        self.print(tabular::search_results(&projects))
+    }
+
+    fn list_outdated(&self, packages: Vec<OutdatedPackage>) -> Result<()> {
+        self.print(tabular::outdated(&packages))
+    }
+
+    fn list_missing(&self, projects: Vec<MissingProject>) -> Result<()> {
+        self.print(tabular::missing(&projects))
+    }
+
+    fn repo_diff(&self, repo_a: &Repo, repo_b: &Repo, entries: Vec<RepoDiffEntry>) -> Result<()> {
+        self.print(tabular::repo_diff(repo_a, repo_b, &entries))
+    }
+
+    fn maintainer_overview(&self, overview: MaintainerOverview) -> Result<()> {
+        self.print(tabular::maintainer_overview(&overview))
+    }
+
+    fn list_history(&self, entries: Vec<HistoryEntry>) -> Result<()> {
+        self.print(tabular::history(&entries))
+    }
+
+    fn list_changes(&self, changes: ChangeSet) -> Result<()> {
+        self.print(tabular::changes(&changes))
+    }
+
+    fn list_problem_groups(&self, group_by: GroupBy, groups: Vec<ProblemGroup>) -> Result<()> {
+        self.print(tabular::problem_groups(group_by, &groups))
+    }
+
+    fn problem_summary(&self, summary: ProblemSummary) -> Result<()> {
+        self.print(tabular::problem_summary(&summary))
     }
 
     fn list_problems(&self, problems: Vec<Problem>) -> Result<()> {
-        let mut table = self.mktable();
-        problems.iter().for_each(|problem| {
-            trace!("Adding row for: {:?}", problem);
-            table.add_row(row![
-                problem.repo(),
-                problem.name(),
-                problem.effname(),
-                problem.maintainer(),
-                problem.problem_description()
-            ]);
-        });
-        self.print(table)
+        self.print(tabular::problems(&problems))
     }
 
     fn compare_packages(&self, packages: Vec<ComparePackage>, backend: &Backend, filter_repos: Vec<Repo>) -> Result<()> {
-        let mut table = self.mktable();
-        for package in packages {
-            backend
-                .project(package.name().deref())?
-                .into_iter()
-                .filter(|p| filter_repos.contains(p.repo()))
-                .for_each(|upstream_package| {
-                    table.add_row(row![
-                       package.name().deref().clone(),
-                         package.version().deref().clone(),
-                         upstream_package.repo().deref().clone(),
-                         upstream_package.version().deref().clone(),
-                   ]);
-                });
-        }
-        self.print(table)
+        let rows = crate::compare::compare_rows(&packages, backend, &filter_repos)?;
+        self.print(tabular::compare(&rows))
     }
 }
-
@@ -0,0 +1,550 @@
+//! The data of the views as plain tables
+//!
+//! Used by the frontends which only print tables and do not need to know about the data types.
+
+use std::fmt;
+use std::ops::Deref;
+
+use failure::Fallible as Result;
+use url::Url;
+use librepology::v1::types::Package;
+use librepology::v1::types::Problem;
+use librepology::v1::types::Repo;
+use librepology::v1::project::Project;
+
+use crate::compare::CompareRow;
+use crate::outdated::OutdatedPackage;
+use crate::missing::MissingProject;
+use crate::repodiff::RepoDiffEntry;
+use crate::maintainer::MaintainerOverview;
+use crate::history::HistoryEntry;
+use crate::changes::ChangeSet;
+use crate::problemkind::ProblemKind;
+use crate::problemreport::GroupBy;
+use crate::problemreport::ProblemGroup;
+use crate::problemreport::ProblemSummary;
+
+/// One cell of a table
+#[derive(Clone)]
+pub enum Cell {
+    Text(String),
+
+    /// A text linking to an URL
+    Link(String, String),
+
+    /// A text linking to the page of a project on repology
+    Project(String, String),
+
+    /// Several values, joined with the list separator of the frontend
+    List(Vec<Cell>),
+}
+
+impl Cell {
+    /// The cell as plain text, without links
+    pub fn plain(&self, list_separator: &str) -> String {
+        match self {
+            Cell::Text(text)       => text.clone(),
+            Cell::Link(text, _)    => text.clone(),
+            Cell::Project(text, _) => text.clone(),
+            Cell::List(cells)      => {
+                cells.iter().map(|c| c.plain(list_separator)).collect::<Vec<_>>().join(list_separator)
+            },
+        }
+    }
+}
+
+/// The URL of the page of a project on repology
+pub fn project_url(repology_url: &Url, project: &str) -> String {
+    let base = repology_url.as_str().trim_end_matches('/');
+    format!("{}/project/{}/versions", base, project)
+}
+
+fn text<T: ToString>(t: T) -> Cell {
+    Cell::Text(t.to_string())
+}
+
+fn optional<T: ToString>(o: Option<T>) -> Cell {
+    Cell::Text(o.map(|t| t.to_string()).unwrap_or_default())
+}
+
+/// A text linking to a project, if both are known
+fn project<T: ToString>(t: Option<T>, project: Option<&String>) -> Cell {
+    match (t, project) {
+        (Some(t), Some(project)) => Cell::Project(t.to_string(), project.clone()),
+        (t, _)                   => optional(t),
+    }
+}
+
+fn list<I: IntoIterator<Item = Cell>>(cells: I) -> Cell {
+    Cell::List(cells.into_iter().collect())
+}
+
+fn link<T: ToString>(url: T) -> Cell {
+    Cell::Link(url.to_string(), url.to_string())
+}
+
+/// The kinds of rows whose columns can be selected with `--columns`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RowKind {
+    Package,
+    Problem,
+    Compare,
+}
+
+impl RowKind {
+    /// All columns of this kind of rows
+    pub fn columns(self) -> &'static [&'static str] {
+        match self {
+            RowKind::Package => PACKAGE_COLUMNS,
+            RowKind::Problem => PROBLEM_HEADERS,
+            RowKind::Compare => COMPARE_HEADERS,
+        }
+    }
+
+    /// The columns printed by frontends with little room
+    fn compact(self) -> &'static [&'static str] {
+        match self {
+            RowKind::Package => &["name", "version", "repo", "status", "www"],
+            RowKind::Problem => &["repo", "name", "effname", "maintainer", "problem"],
+            RowKind::Compare => COMPARE_HEADERS,
+        }
+    }
+}
+
+impl fmt::Display for RowKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RowKind::Package => write!(f, "packages"),
+            RowKind::Problem => write!(f, "problems"),
+            RowKind::Compare => write!(f, "compare rows"),
+        }
+    }
+}
+
+/// The columns selected for one kind of rows, in the order they are printed
+#[derive(Clone, Debug)]
+pub struct Columns {
+    kind: RowKind,
+    names: Vec<String>,
+}
+
+impl Columns {
+    /// Parse a comma-separated list of columns, which must all be columns of `kind`
+    pub fn parse(kind: RowKind, s: &str) -> Result<Self> {
+        let names = s.split(',').map(str::trim).map(String::from).collect::<Vec<_>>();
+
+        match names.iter().find(|n| !kind.columns().contains(&n.as_str())) {
+            Some(n) if n.is_empty() => Err(format_err!("Empty column in '{}'", s)),
+            Some(n) => Err(format_err!("No column '{}' for {}, possible columns: {}", n, kind, kind.columns().join(", "))),
+            None    => Ok(Columns { kind, names }),
+        }
+    }
+}
+
+/// The columns printed if none are selected for a kind of rows
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DefaultColumns {
+    All,
+    Compact,
+}
+
+/// One table of a view, optionally with a heading
+pub struct Section {
+    heading: Option<String>,
+    headers: Vec<String>,
+    rows: Vec<Vec<Cell>>,
+
+    /// The kind of the rows and the number of leading columns which cannot be deselected (like
+    /// the group of grouped problems), if the columns of this section can be selected
+    kind: Option<(RowKind, usize)>,
+}
+
+impl Section {
+    fn new(headers: &[&str]) -> Self {
+        Section {
+            heading: None,
+            headers: headers.iter().map(|h| String::from(*h)).collect(),
+            rows: vec![],
+            kind: None,
+        }
+    }
+
+    fn with_heading<H: Into<String>>(mut self, heading: H) -> Self {
+        self.heading = Some(heading.into());
+        self
+    }
+
+    fn of(mut self, kind: RowKind, fixed: usize) -> Self {
+        self.kind = Some((kind, fixed));
+        self
+    }
+
+    /// Only keep the selected columns, or the default ones if none are selected for these rows
+    ///
+    /// Selecting a column which this section does not have (the project of a plain list of
+    /// packages) is an error, default ones are left out.
+    fn select(self, columns: Option<&Columns>, default: DefaultColumns) -> Result<Self> {
+        let (kind, fixed) = match self.kind {
+            Some(kind) => kind,
+            None       => return Ok(self),
+        };
+
+        let names : Vec<&str> = match (columns.filter(|c| c.kind == kind), default) {
+            (Some(columns), _)              => columns.names.iter().map(String::as_str).collect(),
+            (None, DefaultColumns::All)     => return Ok(self),
+            (None, DefaultColumns::Compact) => {
+                kind.compact().iter().cloned().filter(|n| self.headers.iter().any(|h| h == n)).collect()
+            },
+        };
+
+        let indices = names
+            .iter()
+            .map(|n| {
+                self.headers
+                    .iter()
+                    .skip(fixed)
+                    .position(|h| h == n)
+                    .map(|i| i + fixed)
+                    .ok_or_else(|| {
+                        format_err!("No column '{}' for {} without a project, pass the name of the project: repolocli --stdin project NAME", n, kind)
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let headers = self.headers[..fixed]
+            .iter()
+            .cloned()
+            .chain(names.iter().map(|n| String::from(*n)))
+            .collect();
+
+        let rows = self.rows
+            .iter()
+            .map(|row| {
+                row[..fixed]
+                    .iter()
+                    .cloned()
+                    .chain(indices.iter().map(|i| row[*i].clone()))
+                    .collect()
+            })
+            .collect();
+
+        Ok(Section { headers, rows, ..self })
+    }
+
+    fn push(&mut self, row: Vec<Cell>) {
+        self.rows.push(row);
+    }
+
+    pub fn heading(&self) -> Option<&String> {
+        self.heading.as_ref()
+    }
+
+    pub fn headers(&self) -> &Vec<String> {
+        &self.headers
+    }
+
+    pub fn rows(&self) -> &Vec<Vec<Cell>> {
+        &self.rows
+    }
+}
+
+/// Select the columns of all sections of a view
+pub fn select(sections: Vec<Section>, columns: Option<&Columns>, default: DefaultColumns) -> Result<Vec<Section>> {
+    sections.into_iter().map(|s| s.select(columns, default)).collect()
+}
+
+/// The sections to print
+///
+/// Empty sections are left out, but if all are empty the first one is kept so that its header is
+/// printed.
+pub fn printable(sections: &[Section]) -> Vec<&Section> {
+    if sections.iter().all(|s| s.rows.is_empty()) {
+        sections.iter().take(1).collect()
+    } else {
+        sections.iter().filter(|s| !s.rows.is_empty()).collect()
+    }
+}
+
+const PACKAGE_HEADERS : &[&str] = &[
+    "repo", "name", "srcname", "binname", "visiblename", "version", "status", "summary",
+    "licenses", "maintainers", "www", "downloads",
+];
+
+/// The columns of packages, the project is only known when listing projects
+const PACKAGE_COLUMNS : &[&str] = &[
+    "project", "repo", "name", "srcname", "binname", "visiblename", "version", "status", "summary",
+    "licenses", "maintainers", "www", "downloads",
+];
+
+fn package_row(package: &Package, project_name: Option<&String>) -> Vec<Cell> {
+    vec![
+        text(package.repo().deref()),
+        optional(package.any_name().map(Deref::deref)),
+        optional(package.srcname().map(Deref::deref)),
+        optional(package.binname().map(Deref::deref)),
+        optional(package.visiblename().map(Deref::deref)),
+        text(package.version().deref()),
+        project(package.status(), project_name),
+        optional(package.summary().map(Deref::deref)),
+        list(package.licenses().into_iter().flatten().map(|l| text(l.deref()))),
+        list(package.maintainers().into_iter().flatten().map(|m| text(m.deref()))),
+        list(package.www().into_iter().flatten().map(|w| link(w.deref()))),
+        list(package.downloads().into_iter().flatten().map(|d| link(d.deref()))),
+    ]
+}
+
+const PROBLEM_HEADERS : &[&str] = &["repo", "name", "effname", "maintainer", "kind", "problem"];
+
+const COMPARE_HEADERS : &[&str] = &["package_name", "local_version", "repo", "version", "status"];
+
+fn problem_row(problem: &Problem) -> Vec<Cell> {
+    vec![
+        text(problem.repo().deref()),
+        text(problem.name().deref()),
+        project(Some(problem.effname().deref()), Some(problem.effname().deref())),
+        text(problem.maintainer().deref()),
+        text(ProblemKind::of(problem)),
+        text(problem.problem_description()),
+    ]
+}
+
+pub fn packages(packages: &[Package]) -> Vec<Section> {
+    let mut section = Section::new(PACKAGE_HEADERS).of(RowKind::Package, 0);
+    packages.iter().for_each(|p| section.push(package_row(p, None)));
+    vec![section]
+}
+
+/// One section per project, headed by the project name
+pub fn projects(projects: &[Project]) -> Vec<Section> {
+    let headers = std::iter::once("project").chain(PACKAGE_HEADERS.iter().cloned()).collect::<Vec<_>>();
+
+    projects
+        .iter()
+        .map(|p| {
+            let mut section = Section::new(&headers).with_heading(p.name().clone()).of(RowKind::Package, 0);
+            if !p.is_found() {
+                section.push(not_found_row(&headers, p.name(), "name"));
+            }
+            p.packages().iter().for_each(|package| {
+                let mut row = package_row(package, Some(p.name()));
+                row.insert(0, project(Some(p.name()), Some(p.name())));
+                section.push(row);
+            });
+            section
+        })
+        .collect()
+}
+
+/// A row for a project which was not found, with a marker in `column`
+fn not_found_row<S: AsRef<str>>(headers: &[S], name: &String, column: &str) -> Vec<Cell> {
+    headers
+        .iter()
+        .map(|header| match header.as_ref() {
+            "project"        => project(Some(name), Some(name)),
+            h if h == column => text("<not found>"),
+            _                => text(""),
+        })
+        .collect()
+}
+
+pub fn project_summary(projects: &[Project]) -> Vec<Section> {
+    let mut section = Section::new(&[
+        "project", "newest_version", "newest_devel_version", "repos", "repos_by_status",
+        "maintainers", "licenses",
+    ]);
+
+    projects.iter().for_each(|p| {
+        if !p.is_found() {
+            section.push(not_found_row(&section.headers.clone(), p.name(), "newest_version"));
+            return;
+        }
+
+        let by_status = p.repo_count_by_status()
+            .into_iter()
+            .map(|(status, count)| text(format!("{}: {}", status, count)));
+
+        section.push(vec![
+            project(Some(p.name()), Some(p.name())),
+            optional(p.newest_version().map(Deref::deref)),
+            optional(p.newest_devel_version().map(Deref::deref)),
+            text(p.repos().len()),
+            list(by_status),
+            list(p.maintainers().into_iter().map(|m| text(m.deref()))),
+            list(p.license_consensus().into_iter().flatten().map(|l| text(l.deref()))),
+        ]);
+    });
+
+    vec![section]
+}
+
+pub fn search_results(projects: &[Project]) -> Vec<Section> {
+    let mut section = Section::new(&["project", "newest_version", "repos"]);
+    projects.iter().for_each(|p| {
+        section.push(vec![
+            project(Some(p.name()), Some(p.name())),
+            optional(p.newest_version().map(Deref::deref)),
+            text(p.repos().len()),
+        ]);
+    });
+    vec![section]
+}
+
+pub fn outdated(packages: &[OutdatedPackage]) -> Vec<Section> {
+    let mut section = Section::new(&["project", "name", "version", "newest_version", "newest_repos"]);
+    packages.iter().for_each(|package| {
+        section.push(vec![
+            project(Some(package.project()), Some(package.project())),
+            optional(package.name().map(Deref::deref)),
+            text(package.version().deref()),
+            optional(package.newest_version().map(Deref::deref)),
+            list(package.newest_repos().iter().map(|r| text(r.deref()))),
+        ]);
+    });
+    vec![section]
+}
+
+pub fn missing(projects: &[MissingProject]) -> Vec<Section> {
+    let mut section = Section::new(&["project", "newest_version", "reference_repos"]);
+    projects.iter().for_each(|p| {
+        section.push(vec![
+            project(Some(p.project()), Some(p.project())),
+            optional(p.newest_version().map(Deref::deref)),
+            list(p.reference_repos().iter().map(|r| text(r.deref()))),
+        ]);
+    });
+    vec![section]
+}
+
+pub fn repo_diff(repo_a: &Repo, repo_b: &Repo, entries: &[RepoDiffEntry]) -> Vec<Section> {
+    let mut section = Section::new(&["project", "status", "repo_a", "version_a", "repo_b", "version_b"]);
+    entries.iter().for_each(|entry| {
+        section.push(vec![
+            project(Some(entry.project()), Some(entry.project())),
+            project(Some(entry.status()), Some(entry.project())),
+            text(repo_a.deref()),
+            optional(entry.version_a().map(Deref::deref)),
+            text(repo_b.deref()),
+            optional(entry.version_b().map(Deref::deref)),
+        ]);
+    });
+    vec![section]
+}
+
+pub fn maintainer_overview(overview: &MaintainerOverview) -> Vec<Section> {
+    let mut summary = Section::new(&["key", "value"]).with_heading("Summary");
+    summary.push(vec![text("packages"), text(overview.packages().len())]);
+    overview.packages_by_status().iter().for_each(|(status, count)| {
+        summary.push(vec![text(format!("status: {}", status)), text(count)]);
+    });
+    summary.push(vec![text("problems"), text(overview.problems().len())]);
+
+    let mut packages = Section::new(&[
+        "project", "repo", "name", "version", "status", "newest_version",
+    ]).with_heading("Packages");
+    overview.packages().iter().for_each(|package| {
+        packages.push(vec![
+            project(Some(package.project()), Some(package.project())),
+            text(package.repo().deref()),
+            optional(package.name().map(Deref::deref)),
+            text(package.version().deref()),
+            project(package.status(), Some(package.project())),
+            optional(package.newest_version().map(Deref::deref)),
+        ]);
+    });
+
+    let mut problems = Section::new(PROBLEM_HEADERS).with_heading("Problems").of(RowKind::Problem, 0);
+    overview.problems().iter().for_each(|p| problems.push(problem_row(p)));
+
+    vec![summary, packages, problems]
+}
+
+pub fn history(entries: &[HistoryEntry]) -> Vec<Section> {
+    let mut section = Section::new(&["timestamp", "repo", "name", "old_version", "new_version"]);
+    entries.iter().for_each(|entry| {
+        section.push(vec![
+            text(entry.timestamp().to_rfc3339()),
+            text(entry.repo().deref()),
+            optional(entry.name().map(Deref::deref)),
+            optional(entry.old_version().map(Deref::deref)),
+            optional(entry.new_version().map(Deref::deref)),
+        ]);
+    });
+    vec![section]
+}
+
+pub fn changes(changes: &ChangeSet) -> Vec<Section> {
+    let mut packages = Section::new(&[
+        "change", "project", "repo", "name", "old_version", "old_status", "new_version", "new_status",
+    ]).with_heading("Packages");
+    changes.packages().iter().for_each(|change| {
+        packages.push(vec![
+            text(change.change()),
+            project(change.project(), change.project()),
+            text(change.repo().deref()),
+            optional(change.name().map(Deref::deref)),
+            optional(change.old_version().map(Deref::deref)),
+            project(change.old_status(), change.project()),
+            optional(change.new_version().map(Deref::deref)),
+            project(change.new_status(), change.project()),
+        ]);
+    });
+
+    let headers = std::iter::once("change").chain(PROBLEM_HEADERS.iter().cloned()).collect::<Vec<_>>();
+    let mut problems = Section::new(&headers).with_heading("Problems").of(RowKind::Problem, 1);
+    changes.problems().iter().for_each(|change| {
+        let mut row = problem_row(change.problem());
+        row.insert(0, text(change.change()));
+        problems.push(row);
+    });
+
+    vec![packages, problems]
+}
+
+/// One section per group, all with the same headers
+pub fn problem_groups(group_by: GroupBy, groups: &[ProblemGroup]) -> Vec<Section> {
+    let headers = std::iter::once("group").chain(PROBLEM_HEADERS.iter().cloned()).collect::<Vec<_>>();
+
+    groups
+        .iter()
+        .map(|group| {
+            let heading = format!("{} {} ({})", group_by, group.key(), group.count_text());
+            let mut section = Section::new(&headers).with_heading(heading).of(RowKind::Problem, 1);
+            group.problems().iter().for_each(|p| {
+                let mut row = problem_row(p);
+                row.insert(0, text(group.key()));
+                section.push(row);
+            });
+            section
+        })
+        .collect()
+}
+
+/// The number of problems per kind and per maintainer, in one table
+///
+/// The total is not a row, it would be counted twice when summing up a dimension.
+pub fn problem_summary(summary: &ProblemSummary) -> Vec<Section> {
+    let mut section = Section::new(&["dimension", "key", "problems"]);
+    summary.by_kind().iter().for_each(|(kind, count)| section.push(vec![text("kind"), text(kind), text(count)]));
+    summary.by_maintainer().iter().for_each(|(m, count)| section.push(vec![text("maintainer"), text(m), text(count)]));
+    vec![section]
+}
+
+pub fn problems(problems: &[Problem]) -> Vec<Section> {
+    let mut section = Section::new(PROBLEM_HEADERS).of(RowKind::Problem, 0);
+    problems.iter().for_each(|p| section.push(problem_row(p)));
+    vec![section]
+}
+
+pub fn compare(rows: &[CompareRow]) -> Vec<Section> {
+    let mut section = Section::new(COMPARE_HEADERS).of(RowKind::Compare, 0);
+    rows.iter().for_each(|row| {
+        section.push(vec![
+            project(Some(row.package_name()), Some(row.package_name())),
+            text(row.local_version()),
+            text(row.repo().deref()),
+            optional(row.version().map(Deref::deref)),
+            project(Some(row.status()), Some(row.package_name())),
+        ]);
+    });
+    vec![section]
+}
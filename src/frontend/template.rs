@@ -0,0 +1,261 @@
+use std::io::Stdout;
+use std::io::Write;
+use std::path::Path;
+
+use failure::Fallible as Result;
+use failure::Error;
+use failure::ResultExt;
+use handlebars::Handlebars;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::frontend::serialized::SerializingFrontend;
+use crate::output::Kind;
+
+const TEMPLATE_NAME : &str = "template";
+
+/// A Frontend that renders the data with a user-supplied handlebars template
+///
+/// The template gets the same data as the JSON frontend prints in `data`, so `repolocli -o json`
+/// shows what is available in the template. A single project is passed as its list of packages,
+/// like the templates in `etc/` expect. Output is HTML-escaped if the template file name contains
+/// `.html` or `.htm`, otherwise it is printed as is.
+pub struct TemplateFrontend {
+    stdout: Stdout,
+    handlebars: Handlebars<'static>,
+}
+
+impl TemplateFrontend {
+    pub fn new(stdout: Stdout, path: &Path) -> Result<Self> {
+        let template = std::fs::read_to_string(path)
+            .with_context(|_| format!("Reading template {}", path.display()))?;
+
+        let mut handlebars = Handlebars::new();
+        handlebars
+            .register_template_string(TEMPLATE_NAME, dot_as_this(&template))
+            .with_context(|_| format!("Loading template {}", path.display()))?;
+
+        if !is_html(path) {
+            handlebars.register_escape_fn(handlebars::no_escape);
+        }
+
+        helpers::register(&mut handlebars);
+
+        Ok(TemplateFrontend { stdout, handlebars })
+    }
+}
+
+impl TemplateFrontend {
+    fn render<T: Serialize>(&self, kind: Kind, data: &T) -> Result<String> {
+        let data = match (kind, serde_json::to_value(data)?) {
+            (Kind::Projects, Value::Object(projects)) if projects.len() == 1 => {
+                projects.into_iter().map(|(_, packages)| packages).next().unwrap_or_default()
+            },
+            (_, data) => data,
+        };
+
+        self.handlebars.render(TEMPLATE_NAME, &data).map_err(Error::from)
+    }
+}
+
+impl SerializingFrontend for TemplateFrontend {
+    fn serialize<T: Serialize>(&self, kind: Kind, data: &T) -> Result<()> {
+        let output = self.render(kind, data)?;
+        let mut outlock = self.stdout.lock();
+        write!(outlock, "{}", output).map_err(Error::from)
+    }
+}
+
+fn is_html(path: &Path) -> bool {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .map(|name| name.contains(".html") || name.contains(".htm"))
+        .unwrap_or(false)
+}
+
+/// Replace `.` in expressions with `this`
+///
+/// handlebars.js accepts `.` for the current context (as in `{{#each .}}`), the Rust
+/// implementation does not.
+fn dot_as_this(template: &str) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let expression = &rest[start..];
+        let end = expression.find("}}").map(|end| end + 2).unwrap_or_else(|| expression.len());
+
+        let mut previous = None;
+        let mut chars = expression[..end].chars().peekable();
+        while let Some(c) = chars.next() {
+            let is_start = |c: Option<char>| c.map(|c| c.is_whitespace() || "{~(".contains(c)).unwrap_or(true);
+            let is_end = |c: Option<&char>| c.map(|c| c.is_whitespace() || "}~)".contains(*c)).unwrap_or(true);
+
+            if c == '.' && is_start(previous) && is_end(chars.peek()) {
+                output.push_str("this");
+            } else {
+                output.push(c);
+            }
+            previous = Some(c);
+        }
+
+        rest = &expression[end..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Helpers available in templates
+///
+/// In their own module, because `handlebars_helper!` expects `Result` to be the one from std.
+mod helpers {
+    use std::cmp::Ordering;
+
+    use handlebars::Handlebars;
+    use serde_json::Value;
+
+    use librepology::v1::vercmp;
+
+    pub fn register(handlebars: &mut Handlebars) {
+        handlebars.register_helper("vercmp", Box::new(version_cmp));
+        handlebars.register_helper("version_lt", Box::new(version_lt));
+        handlebars.register_helper("version_eq", Box::new(version_eq));
+        handlebars.register_helper("version_gt", Box::new(version_gt));
+        handlebars.register_helper("status_label", Box::new(status_label));
+    }
+
+    /// Compare two versions the way repology does, `None` if one of them is not a string
+    fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+        Some(vercmp::compare(a.as_str()?, b.as_str()?))
+    }
+
+    handlebars_helper!(version_cmp: |a: Json, b: Json| {
+        match compare(a, b) {
+            Some(Ordering::Less)    => Value::from(-1),
+            Some(Ordering::Equal)   => Value::from(0),
+            Some(Ordering::Greater) => Value::from(1),
+            None                    => Value::Null,
+        }
+    });
+
+    handlebars_helper!(version_lt: |a: Json, b: Json| compare(a, b) == Some(Ordering::Less));
+    handlebars_helper!(version_eq: |a: Json, b: Json| compare(a, b) == Some(Ordering::Equal));
+    handlebars_helper!(version_gt: |a: Json, b: Json| compare(a, b) == Some(Ordering::Greater));
+
+    // A human readable description of a package or compare status
+    handlebars_helper!(status_label: |status: Json| {
+        match status.as_str() {
+            Some("newest")    => "up to date",
+            Some("devel")     => "development version",
+            Some("unique")    => "only in this repository",
+            Some("outdated")  => "outdated",
+            Some("legacy")    => "legacy version",
+            Some("rolling")   => "rolling release",
+            Some("noscheme")  => "no version scheme",
+            Some("incorrect") => "incorrect version",
+            Some("untrusted") => "untrusted version",
+            Some("ignored")   => "ignored",
+            Some("behind")    => "behind",
+            Some("equal")     => "same version",
+            Some("ahead")     => "ahead",
+            Some("missing")   => "missing",
+            Some(other)       => other,
+            None              => "",
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use librepology::v1::project::Project;
+    use librepology::v1::types::Package;
+    use librepology::v1::types::Problem;
+
+    use crate::compare::CompareResult;
+    use crate::frontend::serialized::projects_by_name;
+
+    fn frontend(template: &str) -> TemplateFrontend {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("etc").join(template);
+        TemplateFrontend::new(std::io::stdout(), &path).unwrap()
+    }
+
+    fn packages() -> Vec<Package> {
+        serde_json::from_str(r#"[
+            {"repo": "arch", "name": "foo", "version": "1.2.0", "status": "newest"},
+            {"repo": "debian_12", "name": "foo", "version": "1.0.9", "status": "outdated"}
+        ]"#).unwrap()
+    }
+
+    #[test]
+    fn test_project_template() {
+        let projects = projects_by_name(vec![Project::new(String::from("foo"), packages())]);
+        let output = frontend("project.html").render(Kind::Projects, &projects).unwrap();
+        assert!(output.contains("<td>debian_12</td>"));
+        assert!(output.contains("<td>1.0.9</td>"));
+        assert!(output.contains("<td>outdated</td>"));
+    }
+
+    #[test]
+    fn test_project_template_with_package_list() {
+        let output = frontend("project.html").render(Kind::Packages, &packages()).unwrap();
+        assert!(output.contains("<td>arch</td>"));
+        assert!(output.contains("<td>1.2.0</td>"));
+    }
+
+    #[test]
+    fn test_compare_template() {
+        let results : Vec<CompareResult> = serde_json::from_str(r#"[
+            {"package_name": "foo", "local_version": "1.1", "comparisons": [{"repo": "arch", "version": "1.2.0"}]}
+        ]"#).unwrap();
+        let output = frontend("compare.html").render(Kind::Compare, &results).unwrap();
+        assert!(output.contains("<td>foo</td>"));
+        assert!(output.contains("<td>1.1</td>"));
+        assert!(output.contains("<td>arch</td>"));
+        assert!(output.contains("<td>1.2.0</td>"));
+    }
+
+    #[test]
+    fn test_problems_template() {
+        let problems : Vec<Problem> = serde_json::from_str(r#"[
+            {"repo": "arch", "name": "foo", "effname": "foo", "maintainer": "me@x", "problem": "Homepage link <http://foo.org> is dead"}
+        ]"#).unwrap();
+        let output = frontend("problems.html").render(Kind::Problems, &problems).unwrap();
+        assert!(output.contains("<td>me@x</td>"));
+        assert!(output.contains("<td>Homepage link &lt;http://foo.org&gt; is dead</td>"));
+    }
+
+    #[test]
+    fn test_dot_as_this() {
+        assert_eq!(dot_as_this("{{.}}"), "{{this}}");
+        assert_eq!(dot_as_this("{{ . }}"), "{{ this }}");
+        assert_eq!(dot_as_this("{{#each .}}{{name}}{{/each}}"), "{{#each this}}{{name}}{{/each}}");
+        assert_eq!(dot_as_this("{{~.~}}"), "{{~this~}}");
+        assert_eq!(dot_as_this("{{len (lookup . \"foo\")}}"), "{{len (lookup this \"foo\")}}");
+    }
+
+    #[test]
+    fn test_dot_in_paths_is_kept() {
+        assert_eq!(dot_as_this("{{foo.bar}}"), "{{foo.bar}}");
+        assert_eq!(dot_as_this("{{../name}}"), "{{../name}}");
+        assert_eq!(dot_as_this("{{./name}}"), "{{./name}}");
+        assert_eq!(dot_as_this("{{lookup . \"1.2\"}}"), "{{lookup this \"1.2\"}}");
+    }
+
+    #[test]
+    fn test_dot_outside_expressions_is_kept() {
+        assert_eq!(dot_as_this("Version 1.2. Done."), "Version 1.2. Done.");
+        assert_eq!(dot_as_this(". {{.}} ."), ". {{this}} .");
+        assert_eq!(dot_as_this("{{.}} . {{"), "{{this}} . {{");
+    }
+
+    #[test]
+    fn test_dot_renders_current_context() {
+        let mut handlebars = Handlebars::new();
+        handlebars.register_template_string(TEMPLATE_NAME, dot_as_this("{{#each .}}{{.}};{{/each}}")).unwrap();
+        assert_eq!(handlebars.render(TEMPLATE_NAME, &vec!["a", "b"]).unwrap(), "a;b;");
+    }
+}
@@ -0,0 +1,67 @@
+use std::io::Stdout;
+use std::io::Write;
+
+use failure::Fallible as Result;
+use failure::Error;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::frontend::serialized::SerializingFrontend;
+use crate::output::Envelope;
+use crate::output::Kind;
+use crate::output::Query;
+
+/// A Frontend that serializes the data to TOML
+///
+/// Prints the same envelope as the JSON frontend, which is a table, so that lists of data (which
+/// cannot be a TOML document on their own) end up as `[[data]]` tables. TOML has no null, so null
+/// values are left out. Projects which were not found, which are null in the JSON output, are
+/// listed in `not_found` next to `data` instead.
+pub struct TomlFrontend {
+    stdout: Stdout,
+    query: Query,
+}
+
+impl TomlFrontend {
+    pub fn new(stdout: Stdout, query: Query) -> Self {
+        TomlFrontend { stdout, query }
+    }
+}
+
+impl SerializingFrontend for TomlFrontend {
+    fn serialize<T: Serialize>(&self, kind: Kind, data: &T) -> Result<()> {
+        let mut envelope = serde_json::to_value(Envelope::new(kind, self.query.clone(), data))?;
+        if kind == Kind::Projects || kind == Kind::ProjectSummary {
+            let not_found = envelope["data"]
+                .as_object()
+                .map(|data| data.iter().filter(|(_, v)| v.is_null()).map(|(k, _)| Value::from(k.clone())).collect())
+                .unwrap_or_else(Vec::new);
+
+            envelope["not_found"] = Value::Array(not_found);
+        }
+
+        let output = toml::to_string_pretty(&toml::Value::try_from(without_nulls(envelope))?)?;
+        let mut outlock = self.stdout.lock();
+        write!(outlock, "{}", output).map_err(Error::from)
+    }
+}
+
+fn without_nulls(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object({
+            map.into_iter()
+                .filter(|(_, v)| !v.is_null())
+                .map(|(k, v)| (k, without_nulls(v)))
+                .collect()
+        }),
+
+        Value::Array(values) => Value::Array({
+            values.into_iter()
+                .filter(|v| !v.is_null())
+                .map(without_nulls)
+                .collect()
+        }),
+
+        other => other,
+    }
+}
@@ -0,0 +1,33 @@
+use std::io::Stdout;
+use std::io::Write;
+
+use failure::Fallible as Result;
+use failure::Error;
+use serde::Serialize;
+
+use crate::frontend::serialized::SerializingFrontend;
+use crate::output::Envelope;
+use crate::output::Kind;
+use crate::output::Query;
+
+/// A Frontend that serializes the data to YAML
+///
+/// Prints the same envelope as the JSON frontend.
+pub struct YamlFrontend {
+    stdout: Stdout,
+    query: Query,
+}
+
+impl YamlFrontend {
+    pub fn new(stdout: Stdout, query: Query) -> Self {
+        YamlFrontend { stdout, query }
+    }
+}
+
+impl SerializingFrontend for YamlFrontend {
+    fn serialize<T: Serialize>(&self, kind: Kind, data: &T) -> Result<()> {
+        let output = serde_yaml::to_string(&Envelope::new(kind, self.query.clone(), data))?;
+        let mut outlock = self.stdout.lock();
+        write!(outlock, "{}", output).map_err(Error::from)
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+
+use chrono::DateTime;
+use chrono::Utc;
+
+use librepology::v1::types::Name;
+use librepology::v1::types::Repo;
+use librepology::v1::types::Version;
+use librepology::v1::vercmp;
+
+use crate::snapshot::Snapshot;
+
+/// A change of the version of a package in a repository
+///
+/// A package which appeared has no old version, a package which disappeared has no new version.
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct HistoryEntry {
+    timestamp: DateTime<Utc>,
+    repo: Repo,
+    name: Option<Name>,
+    old_version: Option<Version>,
+    new_version: Option<Version>,
+}
+
+impl HistoryEntry {
+    pub fn timestamp(&self) -> &DateTime<Utc> {
+        &self.timestamp
+    }
+
+    pub fn repo(&self) -> &Repo {
+        &self.repo
+    }
+
+    pub fn name(&self) -> Option<&Name> {
+        self.name.as_ref()
+    }
+
+    pub fn old_version(&self) -> Option<&Version> {
+        self.old_version.as_ref()
+    }
+
+    pub fn new_version(&self) -> Option<&Version> {
+        self.new_version.as_ref()
+    }
+}
+
+type PackageKey = (Repo, Option<Name>);
+
+/// The version of each package in a snapshot
+///
+/// Packages are identified by repository and name. If a repository has several packages with the
+/// same name, the highest version is used.
+fn versions(snapshot: &Snapshot) -> BTreeMap<PackageKey, Version> {
+    let mut map : BTreeMap<PackageKey, Version> = BTreeMap::new();
+    for package in snapshot.packages() {
+        let key = (package.repo().clone(), package.any_name().cloned());
+        let version = package.version();
+
+        let is_newer = map
+            .get(&key)
+            .map(|v| vercmp::compare(version, v) == std::cmp::Ordering::Greater)
+            .unwrap_or(true);
+
+        if is_newer {
+            map.insert(key, version.clone());
+        }
+    }
+    map
+}
+
+/// Compute the version changes from a list of snapshots, which must be sorted oldest first
+///
+/// All packages of the first snapshot are reported as appeared at the time of that snapshot.
+pub fn history(snapshots: &[Snapshot]) -> Vec<HistoryEntry> {
+    let mut entries = vec![];
+    let mut previous : BTreeMap<PackageKey, Version> = BTreeMap::new();
+
+    for snapshot in snapshots {
+        let current = versions(snapshot);
+        let entry = |(repo, name): &PackageKey, old: Option<&Version>, new: Option<&Version>| HistoryEntry {
+            timestamp: *snapshot.timestamp(),
+            repo: repo.clone(),
+            name: name.clone(),
+            old_version: old.cloned(),
+            new_version: new.cloned(),
+        };
+
+        for (key, version) in current.iter() {
+            match previous.get(key) {
+                Some(old) if old == version => {},
+                old => entries.push(entry(key, old, Some(version))),
+            }
+        }
+
+        for (key, version) in previous.iter() {
+            if !current.contains_key(key) {
+                entries.push(entry(key, Some(version), None));
+            }
+        }
+
+        previous = current;
+    }
+
+    // additions and removals of one snapshot were collected separately
+    entries.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.repo.cmp(&b.repo)));
+    entries
+}
@@ -0,0 +1,139 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io::Read;
+
+use failure::Fallible as Result;
+use failure::Error;
+use serde_json::Value;
+
+use librepology::v1::api::Api;
+use librepology::v1::filter::ProjectFilter;
+use librepology::v1::project::Project;
+use librepology::v1::types::Package;
+use librepology::v1::types::Problem;
+
+use crate::output::Kind;
+use crate::problemreport::ProblemGroup;
+
+/// Data passed on stdin with `--stdin`
+///
+/// Accepts the JSON output of repolocli as well as responses of the repology API, so that the
+/// output of one invocation can be the input of the next. Stdin is read once, on first use.
+#[derive(Default)]
+pub struct StdinInput(RefCell<Option<(Option<Kind>, Value)>>);
+
+impl StdinInput {
+    fn read(&self) -> Result<(Option<Kind>, Value)> {
+        if let Some(data) = self.0.borrow().as_ref() {
+            return Ok(data.clone())
+        }
+
+        let mut buffer = String::new();
+        let read = std::io::stdin().read_to_string(&mut buffer)?;
+        trace!("Read {} bytes from stdin", read);
+
+        let data = crate::output::open(serde_json::from_str(&buffer)?)?;
+        *self.0.borrow_mut() = Some(data.clone());
+        Ok(data)
+    }
+
+    /// The names of the projects on stdin, if it contains several projects
+    pub fn project_names(&self) -> Result<Option<Vec<String>>> {
+        match self.read()? {
+            (None, Value::Object(map)) | (Some(Kind::Projects), Value::Object(map)) => {
+                Ok(Some(map.keys().cloned().collect()))
+            },
+            _ => Ok(None),
+        }
+    }
+}
+
+impl Api for StdinInput {
+    /// The packages of a project
+    ///
+    /// If stdin contains several projects, the packages of the named one, or of all if the name is
+    /// empty.
+    fn project<N: AsRef<str>>(&self, name: N) -> Result<Vec<Package>> {
+        let projects = match self.read()? {
+            (None, Value::Array(packages)) | (Some(Kind::Packages), Value::Array(packages)) => {
+                return serde_json::from_value(Value::Array(packages)).map_err(Error::from)
+            },
+
+            (None, data) | (Some(Kind::Projects), data) => {
+                serde_json::from_value::<BTreeMap<String, Option<Vec<Package>>>>(data)?
+            },
+
+            (Some(kind), _) => return Err(format_err!("Cannot read packages from {} output", kind)),
+        };
+
+        let name = name.as_ref();
+        if name.is_empty() {
+            Ok(projects.into_values().flat_map(Option::unwrap_or_default).collect())
+        } else {
+            Ok(projects.get(name).cloned().unwrap_or_default().unwrap_or_default())
+        }
+    }
+
+    fn problems_for_repo<R: AsRef<str>>(&self, _repo: R) -> Result<Vec<Problem>> {
+        problems(self.read()?)
+    }
+
+    fn problems_for_maintainer<M: AsRef<str>>(&self, _maintainer: M) -> Result<Vec<Problem>> {
+        problems(self.read()?)
+    }
+
+    /// All data on stdin is returned as the first page, so asking for any later page (by passing
+    /// `start`) yields no projects.
+    fn projects(&self, start: Option<&str>, _filter: &ProjectFilter) -> Result<Vec<Project>> {
+        if start.is_some() {
+            return Ok(vec![])
+        }
+
+        match self.read()? {
+            (None, data) | (Some(Kind::Projects), data) => {
+                serde_json::from_value::<BTreeMap<String, Option<Vec<Package>>>>(data)
+                    .map(|projects| {
+                        projects
+                            .into_iter()
+                            .map(|(name, packages)| match packages {
+                                Some(packages) => Project::new(name, packages),
+                                None           => Project::not_found(name),
+                            })
+                            .collect()
+                    })
+                    .map_err(Error::from)
+            },
+
+            (Some(kind), _) => Err(format_err!("Cannot read projects from {} output", kind)),
+        }
+    }
+}
+
+/// Problems from a list of problems, or from outputs which contain problems
+fn problems((kind, data): (Option<Kind>, Value)) -> Result<Vec<Problem>> {
+    #[derive(Deserialize)]
+    struct WithProblems {
+        problems: Vec<Problem>,
+    }
+
+    #[derive(Deserialize)]
+    struct Groups {
+        groups: Vec<ProblemGroup>,
+    }
+
+    match kind {
+        None | Some(Kind::Problems) => serde_json::from_value(data).map_err(Error::from),
+
+        Some(Kind::MaintainerOverview) => {
+            serde_json::from_value::<WithProblems>(data).map(|o| o.problems).map_err(Error::from)
+        },
+
+        Some(Kind::ProblemGroups) => {
+            serde_json::from_value::<Groups>(data)
+                .map(|g| g.groups.into_iter().flat_map(ProblemGroup::into_problems).collect())
+                .map_err(Error::from)
+        },
+
+        Some(kind) => Err(format_err!("Cannot read problems from {} output", kind)),
+    }
+}
@@ -0,0 +1,124 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use failure::Error;
+use failure::Fallible as Result;
+use semver::Version as SemverVersion;
+
+use librepology::v1::types::Package;
+use librepology::v1::types::Status;
+use librepology::v1::vercmp;
+
+/// How to find the latest version of a project
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum LatestStrategy {
+    /// Trust repology: Use the packages repology marked as newest (or devel)
+    Status,
+
+    /// Compare versions the way repology does it
+    Version,
+
+    /// Compare versions as semver, ignoring packages with versions that are not valid semver
+    Semver,
+
+    /// Compare versions as plain strings
+    String,
+}
+
+impl FromStr for LatestStrategy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "status"  => Ok(LatestStrategy::Status),
+            "version" => Ok(LatestStrategy::Version),
+            "semver"  => Ok(LatestStrategy::Semver),
+            "string"  => Ok(LatestStrategy::String),
+            other     => Err(format_err!("Unknown strategy for finding the latest version: {}", other)),
+        }
+    }
+}
+
+/// Whether repology itself would consider the version of the package when finding the newest one
+fn is_candidate(package: &Package, include_devel: bool) -> bool {
+    match package.status() {
+        Some(Status::Newest)  => true,
+        Some(Status::Devel)   => include_devel,
+        Some(Status::Unique)
+            | Some(Status::Outdated)
+            | Some(Status::Legacy) => true,
+        Some(_) => false, // rolling, noscheme, incorrect, untrusted, ignored
+        None    => true,
+    }
+}
+
+/// Find all packages which carry the latest version of a project
+///
+/// As several repositories usually carry the same version, this returns all of them.
+pub fn latest(packages: Vec<Package>, strategy: LatestStrategy, include_devel: bool) -> Vec<Package> {
+    let candidates = packages
+        .into_iter()
+        .filter(|p| match strategy {
+            LatestStrategy::Status => match p.status() {
+                Some(Status::Newest) => true,
+                Some(Status::Devel)  => include_devel,
+                _                    => false,
+            },
+            _ => is_candidate(p, include_devel),
+        });
+
+    match strategy {
+        LatestStrategy::Status | LatestStrategy::Version => {
+            max_by(candidates.collect(), |a, b| vercmp::compare(a.version(), b.version()))
+        },
+
+        LatestStrategy::Semver => {
+            let candidates = candidates
+                .filter_map(|p| match SemverVersion::parse(p.version()) {
+                    Ok(v) => Some((v, p)),
+                    Err(e) => {
+                        trace!("Ignoring {:?}, not semver: {}", p.version(), e);
+                        None
+                    }
+                })
+                .collect();
+
+            max_by(candidates, |a, b| a.0.cmp(&b.0))
+                .into_iter()
+                .map(|(_, p)| p)
+                .collect()
+        },
+
+        LatestStrategy::String => {
+            max_by(candidates.collect(), |a, b| a.version().cmp(b.version()))
+        },
+    }
+}
+
+/// Get all elements which are equal to the maximum element
+fn max_by<T, F>(elements: Vec<T>, cmp: F) -> Vec<T>
+    where F: Fn(&T, &T) -> Ordering
+{
+    let max_idx = elements
+        .iter()
+        .enumerate()
+        .max_by(|a, b| cmp(a.1, b.1))
+        .map(|(idx, _)| idx);
+
+    match max_idx {
+        None => vec![],
+        Some(idx) => {
+            let is_max = elements
+                .iter()
+                .map(|e| cmp(e, &elements[idx]) == Ordering::Equal)
+                .collect::<Vec<bool>>();
+
+            elements
+                .into_iter()
+                .zip(is_max)
+                .filter(|(_, is_max)| *is_max)
+                .map(|(e, _)| e)
+                .collect()
+        }
+    }
+}
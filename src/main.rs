@@ -6,25 +6,52 @@ extern crate xdg;
 extern crate flexi_logger;
 extern crate filters;
 extern crate boolinator;
-extern crate itertools;
 extern crate semver;
-
-#[cfg(feature = "compare_csv")]
+extern crate strsim;
+extern crate atty;
+extern crate chrono;
+extern crate humantime;
+extern crate terminal_size;
+extern crate unicode_width;
+
+#[cfg(any(feature = "compare_csv", feature = "output_csv"))]
 extern crate csv;
 
 #[macro_use] extern crate serde_derive;
 #[macro_use] extern crate log;
 #[macro_use] extern crate failure;
-#[macro_use] extern crate prettytable;
+extern crate prettytable;
+#[cfg(feature = "output_template")]
+#[macro_use] extern crate handlebars;
+#[cfg(feature = "output_yaml")]
+extern crate serde_yaml;
 
 mod config;
 mod backend;
 mod frontend;
 mod cli;
 mod compare;
-
+mod latest;
+mod sort;
+mod search;
+mod outdated;
+mod missing;
+mod repodiff;
+mod maintainer;
+mod snapshot;
+mod history;
+mod changes;
+mod watch;
+mod problemkind;
+mod ack;
+mod problemreport;
+mod output;
+mod input;
+
+use std::collections::HashSet;
 use std::path::PathBuf;
-use std::cmp::Ordering;
+use std::io::Read;
+use std::io::Write;
 
 #[cfg(feature = "compare_csv")]
 use std::io::Cursor;
@@ -36,14 +63,30 @@ use failure::Fallible as Result;
 use clap::ArgMatches;
 use filters::filter::Filter;
 use boolinator::Boolinator;
-use itertools::Itertools;
-use semver::Version as SemverVersion;
 
+use backend::Backend;
 use config::Configuration;
 use compare::ComparePackage;
+use latest::LatestStrategy;
+use sort::SortSpec;
+use sort::Sortable;
+use outdated::OutdatedPackage;
+use missing::MissingProject;
+use maintainer::MaintainerOverview;
+use changes::Capture;
+use problemkind::ProblemKind;
+use ack::AckStore;
+use ack::Acknowledgement;
+use problemreport::GroupBy;
+use problemreport::ProblemSummary;
+use snapshot::Snapshot;
+use snapshot::SnapshotStore;
 use librepology::v1::api::Api;
-use librepology::v1::types::Repo;
+use librepology::v1::project::Project;
+use librepology::v1::filter::ProjectFilter;
 use librepology::v1::types::Package;
+use librepology::v1::types::Problem;
+use librepology::v1::types::Repo;
 
 fn initialize_logging(app: &ArgMatches) -> Result<()> {
     let verbosity = app.occurrences_of("verbose");
@@ -104,6 +147,87 @@ fn deserialize_package_list(s: String, filepath: &str) -> Result<Vec<ComparePack
     }
 }
 
+/// Collect the project names passed on the commandline, from a file or from stdin
+///
+/// Lists of names are newline-separated, empty lines and lines starting with '#' are ignored.
+fn project_names(backend: &Backend, mtch: &ArgMatches) -> Result<Vec<String>> {
+    if let Backend::Stdin(input) = backend {
+        // With "--stdin", the projects are the ones on stdin. A plain list of packages has no
+        // project name: it is the project named on the commandline if there is exactly one,
+        // otherwise the empty name gets all packages on stdin.
+        return Ok(input.project_names()?.unwrap_or_else(|| {
+            let names = mtch.values_of("project_name").map(|v| v.collect::<Vec<_>>()).unwrap_or_default();
+            match names.as_slice() {
+                [name] if *name != "-" => vec![String::from(*name)],
+                _                      => vec![String::new()],
+            }
+        }))
+    }
+
+    let read_list = |path: &str| -> Result<Vec<String>> {
+        let content = if path == "-" {
+            let mut buffer = String::new();
+            std::io::stdin().read_to_string(&mut buffer)?;
+            buffer
+        } else {
+            std::fs::read_to_string(path)?
+        };
+
+        Ok(content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(String::from)
+            .collect())
+    };
+
+    let mut names = vec![];
+    for name in mtch.values_of("project_name").into_iter().flatten() {
+        if name == "-" {
+            names.append(&mut read_list(name)?);
+        } else {
+            names.push(String::from(name));
+        }
+    }
+
+    if let Some(path) = mtch.value_of("from-file") {
+        names.append(&mut read_list(path)?);
+    }
+
+    if names.is_empty() {
+        Err(format_err!("No project name given"))
+    } else {
+        Ok(names)
+    }
+}
+
+/// The sort spec passed on the commandline or, if none was passed, the default from the
+/// configuration
+///
+/// The spec is validated for `T`, so it can be used to sort lists of `T` later on.
+fn sort_spec<T: Sortable>(mtch: &ArgMatches, default: Option<&String>) -> Result<Option<SortSpec>> {
+    let deprecated = [("sort-version", "version"), ("sort-repo", "repo"), ("sort-maintainer", "maintainer")]
+        .iter()
+        .find(|(flag, _)| mtch.is_present(flag))
+        .map(|(flag, key)| {
+            warn!("--{} is deprecated, use --sort {}", flag, key);
+            *key
+        });
+
+    match mtch.value_of("sort").or(deprecated).or_else(|| default.map(String::as_str)) {
+        Some(spec) => {
+            trace!("Sorting by '{}'", spec);
+            let spec = spec.parse::<SortSpec>()?;
+            spec.validate::<T>()?;
+            Ok(Some(spec))
+        },
+        None => {
+            trace!("Not sorting");
+            Ok(None)
+        },
+    }
+}
+
 fn app() -> Result<()> {
     let app = cli::build_cli().get_matches();
     initialize_logging(&app)?;
@@ -167,104 +291,471 @@ fn app() -> Result<()> {
     match app.subcommand() {
         ("project", Some(mtch)) => {
             debug!("Subcommand: 'project'");
-            trace!("sort-versions:   {}", mtch.is_present("sort-version"));
-            trace!("sort-repository: {}", mtch.is_present("sort-repo"));
-
-            let name = if app.is_present("input_stdin") {
-                // Ugly, but works:
-                // If we have "--stdin" on CLI, we have a CLI/Stdin backend, which means that we can query
-                // _any_ "project", and get the stdin anyways. This is really not like it should be, but
-                // works for now
-                ""
+
+            let names = project_names(&backend, mtch)?;
+            trace!("Projects: {:?}", names);
+            let interactive = !app.is_present("input_stdin") && atty::is(atty::Stream::Stdout);
+
+            let latest = if !mtch.is_present("latest") {
+                None
+            } else if mtch.is_present("semver") {
+                warn!("--semver is deprecated, use --latest-by semver");
+                Some(LatestStrategy::Semver)
             } else {
-                mtch.value_of("project_name").unwrap()  // safe by clap
+                Some(mtch.value_of("latest-by").unwrap_or("status").parse::<LatestStrategy>()?)
             };
+            let include_devel = mtch.is_present("include-devel");
+            trace!("Finding latest packages with {:?}, include devel = {}", latest, include_devel);
+            let sort = sort_spec::<Package>(mtch, config.sort().project())?;
 
-            let mut packages: Vec<Package> = {
-                debug!("Fetching packages");
-                let iter = backend
-                    .project(&name)?
-                    .into_iter()
-                    .filter(|package| repository_filter.filter(package.repo()));
-
-                if mtch.is_present("sort-version"){
-                    trace!("Sorting by version");
-                    iter.sorted_by(|a, b| Ord::cmp(a.version(), b.version()))
-                        .collect()
-                } else if mtch.is_present("sort-repo") {
-                    trace!("Sorting by repository");
-                    iter.sorted_by(|a, b| Ord::cmp(a.repo(), b.repo()))
-                        .collect()
-                } else {
-                    trace!("Not sorting");
-                    iter.collect()
-                }
-            };
+            let mut projects = names
+                .into_iter()
+                .map(|name| {
+                    debug!("Fetching packages for '{}'", name);
+                    let packages = backend.project(&name)?;
+
+                    // Whether a project exists is decided before any filtering, a project whose
+                    // packages are all filtered out is still listed as found
+                    if packages.is_empty() {
+                        if interactive {
+                            // Suggestions are best-effort, failing to find any is not an error
+                            match search::suggestions(&backend, &name) {
+                                Ok(ref suggestions) if suggestions.is_empty() => {},
+                                Ok(suggestions) => {
+                                    warn!("Project '{}' not found, did you mean: {}", name, suggestions.join(", "));
+                                },
+                                Err(e) => warn!("Project '{}' not found, cannot look for similar names: {}", name, e),
+                            }
+                        }
 
-            let packages = if mtch.is_present("latest") {
-                if mtch.is_present("semver") {
-                    let comp = |a: &Package, b: &Package| {
-                        let av = SemverVersion::parse(a.version());
-                        let bv = SemverVersion::parse(b.version());
+                        return Ok(Project::not_found(name))
+                    }
 
-                        if let (Ok(av), Ok(bv)) = (av, bv) {
-                            av.partial_cmp(&bv).unwrap_or(Ordering::Equal)
-                        } else {
-                            Ordering::Equal
-                        }
+                    let packages = packages
+                        .into_iter()
+                        .filter(|package| repository_filter.filter(package.repo()))
+                        .collect();
+
+                    let mut packages = match latest {
+                        Some(strategy) => latest::latest(packages, strategy, include_devel),
+                        None           => packages,
                     };
 
-                    packages.sort_by(comp);
-                } else {
-                    packages.sort_by(|a, b| a.version().partial_cmp(b.version()).unwrap_or(Ordering::Equal));
-                }
-                packages.pop().into_iter().collect::<Vec<_>>()
+                    if let Some(spec) = sort.as_ref() {
+                        spec.sort(&mut packages);
+                    }
+                    Ok(Project::new(name, packages))
+                })
+                .collect::<Result<Vec<Project>>>()?;
+
+            if mtch.is_present("summary") {
+                debug!("Listing project summaries in frontend");
+                frontend.project_summary(projects)
+            } else if projects.len() == 1 && projects[0].name().is_empty() {
+                // A plain list of packages on stdin, which has no project
+                debug!("Listing packages in frontend");
+                frontend.list_packages(projects.pop().unwrap().into_packages()) // safe by check above
             } else {
-                packages
-            };
-
-            debug!("Listing packages in frontend");
-            frontend.list_packages(packages)
+                debug!("Listing projects in frontend");
+                frontend.list_projects(projects)
+            }
         },
 
         ("problems", Some(mtch)) => {
             debug!("Subcommand: 'problems'");
 
+            match mtch.subcommand() {
+                ("ack", Some(ackmtch)) => {
+                    let repo = ackmtch.value_of("repo").unwrap(); // safe by clap
+                    let name = ackmtch.value_of("name").unwrap(); // safe by clap
+                    let kind = ackmtch.value_of("kind").unwrap().parse::<ProblemKind>()?; // safe by clap
+
+                    // Acknowledge the current description of the problem, so it can expire
+                    let all = backend.problems_for_repo(repo)?;
+                    let (problems, others) = all
+                        .iter()
+                        .filter(|p| **p.name() == name)
+                        .partition::<Vec<_>, _>(|p| ack::is_problem(p, repo, name, kind));
+
+                    if problems.is_empty() {
+                        let mut kinds = others.iter().map(|p| ProblemKind::of(p).to_string()).collect::<Vec<_>>();
+                        kinds.sort();
+                        kinds.dedup();
+
+                        return if kinds.is_empty() {
+                            Err(format_err!("No problems of '{}' in {}", name, repo))
+                        } else {
+                            Err(format_err!("No {} problem of '{}' in {}, but: {}", kind, name, repo, kinds.join(", ")))
+                        }
+                    }
+
+                    let mut store = AckStore::open_default()?;
+                    store.expire(&all).iter().for_each(|ack| {
+                        info!("Removed expired acknowledgement of {} problem of '{}' in {}", ack.kind(), ack.name(), ack.repo());
+                    });
+
+                    let timestamp = chrono::Utc::now();
+                    for problem in problems {
+                        info!("Acknowledged: {}", problem.problem_description());
+                        store.ack(Acknowledgement::new(problem, ackmtch.value_of("reason").map(String::from), timestamp));
+                    }
+
+                    return store.save()
+                },
+
+                ("unack", Some(ackmtch)) => {
+                    let repo = ackmtch.value_of("repo").unwrap(); // safe by clap
+                    let name = ackmtch.value_of("name").unwrap(); // safe by clap
+                    let kind = ackmtch.value_of("kind").unwrap().parse::<ProblemKind>()?; // safe by clap
+
+                    let mut store = AckStore::open_default()?;
+                    let removed = store.unack(repo, name, kind);
+                    if removed.is_empty() {
+                        return Err(format_err!("No acknowledged {} problem of '{}' in {}", kind, name, repo))
+                    }
+                    info!("Removed {} acknowledgements", removed.len());
+
+                    return store.save()
+                },
+
+                _ => {},
+            }
+
             let repo = mtch.value_of("repo");
             let maintainer = mtch.value_of("maintainer");
 
             trace!("repo       = {:?}", repo);
             trace!("maintainer = {:?}", maintainer);
 
-            let problems = {
+            let mut problems = {
                 debug!("Finding problems...");
-                let iter = match (repo, maintainer) {
+                match (repo, maintainer) {
                     (Some(r), None) => backend.problems_for_repo(&r)?,
                     (None, Some(m)) => backend.problems_for_maintainer(&m)?,
                     (None, None) => unimplemented!(),
                     (Some(_), Some(_)) => unimplemented!(),
                 }
                 .into_iter()
-                .filter(|problem| repository_filter.filter(problem.repo()));
-
-                if mtch.is_present("sort-maintainer") {
-                    trace!("Sorting problems by maintainer");
-                    iter.sorted_by(|a, b| Ord::cmp(a.maintainer(), b.maintainer()))
-                        .collect()
-                } else if mtch.is_present("sort-repo") {
-                    trace!("Sorting problems by repo");
-                    iter.sorted_by(|a, b| Ord::cmp(a.repo(), b.repo()))
-                        .collect()
-                } else {
-                    trace!("Not sorting problems");
-                    iter.collect()
-                }
+                .filter(|problem| repository_filter.filter(problem.repo()))
+                .collect::<Vec<_>>()
             };
 
+            // Listing problems never changes the store, expired acknowledgements are only removed
+            // by "problems ack"
+            let acks = AckStore::load_default()?;
+            acks.expired(&problems).iter().for_each(|ack| {
+                info!("Acknowledgement of {} problem of '{}' in {} expired, the problem changed", ack.kind(), ack.name(), ack.repo());
+            });
+
+            if !mtch.is_present("show-acked") {
+                let count = problems.len();
+                problems.retain(|problem| !acks.is_acked(problem));
+                if count != problems.len() {
+                    info!("{} acknowledged problems not listed, use --show-acked to list them", count - problems.len());
+                }
+            }
+
+            if mtch.is_present("summary") {
+                debug!("Listing problem summary in frontend");
+                return frontend.problem_summary(ProblemSummary::new(&problems))
+            }
+
+            if let Some(spec) = sort_spec::<Problem>(mtch, config.sort().problems())? {
+                spec.sort(&mut problems);
+            }
+
+            if let Some(group_by) = mtch.value_of("group-by") {
+                let group_by = group_by.parse::<GroupBy>()?;
+                debug!("Listing problems grouped by {} in frontend", group_by);
+                return frontend.list_problem_groups(group_by, problemreport::group(problems, group_by))
+            }
+
             debug!("Listing problems in frontend");
             frontend.list_problems(problems)
         },
 
+        ("search", Some(mtch)) => {
+            debug!("Subcommand: 'search'");
+            let term = mtch.value_of("term").unwrap(); // safe by clap
+            let repos = mtch.values_of("repo")
+                .map(|repos| repos.map(String::from).collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            trace!("term  = {}", term);
+            trace!("repos = {:?}", repos);
+
+            debug!("Searching projects...");
+            let projects = search::search(&backend, term, &repos)?;
+
+            debug!("Listing search results in frontend");
+            frontend.list_search_results(projects)
+        },
+
+        ("outdated", Some(mtch)) => {
+            debug!("Subcommand: 'outdated'");
+            let repo = Repo::new(mtch.value_of("repo").map(String::from).unwrap()); // safe by clap
+            let maintainer = mtch.value_of("maintainer");
+
+            trace!("repo       = {:?}", repo);
+            trace!("maintainer = {:?}", maintainer);
+
+            let filter = {
+                let filter = ProjectFilter::new().inrepo(repo.as_str()).outdated(true);
+                match maintainer {
+                    Some(m) => filter.maintainer(m),
+                    None    => filter,
+                }
+            };
+
+            debug!("Finding outdated projects...");
+            let pages = backend
+                .project_pages(filter)
+                .map(|page| {
+                    page.map(|projects| {
+                        projects
+                            .iter()
+                            .flat_map(|project| OutdatedPackage::from_project(project, &repo, maintainer))
+                            .map(|package| package.filter_newest_repos(|repo| repository_filter.filter(repo)))
+                            .collect()
+                    })
+                });
+
+            debug!("Listing outdated packages in frontend");
+            frontend.list_outdated_pages(Box::new(pages))
+        },
+
+        ("missing", Some(mtch)) => {
+            debug!("Subcommand: 'missing'");
+            let repo = mtch.value_of("repo").unwrap(); // safe by clap
+            let references = mtch.values_of("reference")
+                .unwrap() // safe by clap
+                .map(String::from)
+                .map(Repo::new)
+                .collect::<Vec<_>>();
+            let newest_only = mtch.is_present("newest");
+
+            trace!("repo        = {}", repo);
+            trace!("references  = {:?}", references);
+            trace!("newest only = {}", newest_only);
+
+            let shown_references = references
+                .iter()
+                .filter(|r| repository_filter.filter(r))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            // A project in several reference repositories is found once per reference, list it once
+            let mut seen = HashSet::new();
+            let pages = references
+                .iter()
+                .flat_map(|reference| {
+                    debug!("Finding projects in '{}' which are missing in '{}'", reference.as_str(), repo);
+                    let filter = ProjectFilter::new()
+                        .inrepo(reference.as_str())
+                        .notinrepo(repo)
+                        .newest(newest_only);
+
+                    backend.project_pages(filter)
+                })
+                .map(move |page| {
+                    page.map(|projects| {
+                        projects
+                            .iter()
+                            .filter(|project| seen.insert(project.name().clone()))
+                            .map(|project| MissingProject::from_project(project, &shown_references, newest_only))
+                            .filter(|missing| !missing.reference_repos().is_empty())
+                            .collect()
+                    })
+                });
+
+            debug!("Listing missing projects in frontend");
+            frontend.list_missing_pages(Box::new(pages))
+        },
+
+        ("repo-diff", Some(mtch)) => {
+            debug!("Subcommand: 'repo-diff'");
+            let repo_a = Repo::new(mtch.value_of("repo_a").map(String::from).unwrap()); // safe by clap
+            let repo_b = Repo::new(mtch.value_of("repo_b").map(String::from).unwrap()); // safe by clap
+
+            trace!("repo a = {:?}", repo_a);
+            trace!("repo b = {:?}", repo_b);
+
+            debug!("Fetching projects in '{}'", repo_a.as_str());
+            let projects_a = backend.all_projects(&ProjectFilter::new().inrepo(repo_a.as_str()))?;
+            debug!("Fetching projects in '{}'", repo_b.as_str());
+            let projects_b = backend.all_projects(&ProjectFilter::new().inrepo(repo_b.as_str()))?;
+
+            debug!("Diffing repositories");
+            let entries = repodiff::diff(&repo_a, &repo_b, projects_a, projects_b);
+
+            debug!("Listing repository diff in frontend");
+            frontend.repo_diff(&repo_a, &repo_b, entries)
+        },
+
+        ("maintainer", Some(mtch)) => {
+            debug!("Subcommand: 'maintainer'");
+            let maintainer = mtch.value_of("maintainer").unwrap(); // safe by clap
+            trace!("maintainer = {}", maintainer);
+
+            debug!("Fetching projects of '{}'", maintainer);
+            let projects = backend.all_projects(&ProjectFilter::new().maintainer(maintainer))?;
+
+            debug!("Fetching problems of '{}'", maintainer);
+            let problems = backend
+                .problems_for_maintainer(maintainer)?
+                .into_iter()
+                .filter(|problem| repository_filter.filter(problem.repo()))
+                .collect();
+
+            let overview = MaintainerOverview::new(String::from(maintainer), projects, problems, |package| {
+                repository_filter.filter(package.repo())
+            });
+
+            debug!("Listing maintainer overview in frontend");
+            frontend.maintainer_overview(overview)
+        },
+
+        ("snapshot", Some(mtch)) => {
+            debug!("Subcommand: 'snapshot'");
+            if app.is_present("input_stdin") {
+                return Err(format_err!("Cannot record snapshots of data from stdin"))
+            }
+
+            let names = match mtch.values_of("project_name") {
+                Some(names) => names.map(String::from).collect::<Vec<_>>(),
+                None        => config.watchlist().clone(),
+            };
+
+            if names.is_empty() {
+                return Err(format_err!("No projects passed and watchlist in configuration is empty"))
+            }
+
+            let store = SnapshotStore::open_default()?;
+            let timestamp = chrono::Utc::now();
+
+            for name in names {
+                debug!("Recording snapshot of '{}'", name);
+                let packages = backend.project(&name)?;
+                if store.record(&Snapshot::new(timestamp, name.clone(), packages))? {
+                    info!("Recorded snapshot of '{}'", name);
+                } else {
+                    info!("'{}' did not change since the last snapshot", name);
+                }
+            }
+
+            Ok(())
+        },
+
+        ("history", Some(mtch)) => {
+            debug!("Subcommand: 'history'");
+            let name = mtch.value_of("project_name").unwrap(); // safe by clap
+
+            let snapshots = SnapshotStore::open_default()?.load(name)?;
+            if snapshots.is_empty() {
+                return Err(format_err!("No snapshots of '{}' recorded, see 'repolocli snapshot'", name))
+            }
+
+            let entries = history::history(&snapshots)
+                .into_iter()
+                .filter(|entry| repository_filter.filter(entry.repo()))
+                .collect();
+
+            debug!("Listing history in frontend");
+            frontend.list_history(entries)
+        },
+
+        ("diff", Some(mtch)) => {
+            debug!("Subcommand: 'diff'");
+            let read = |arg: &str| -> Result<Capture> {
+                let path = mtch.value_of(arg).unwrap(); // safe by clap
+                let buffer = std::fs::read_to_string(path)?;
+                Capture::from_json(&buffer)
+                    .with_context(|_| format!("Reading {}", path))
+                    .map_err(Error::from)
+            };
+
+            let changes = changes::diff(read("old")?, read("new")?)?;
+
+            debug!("Listing changes in frontend");
+            frontend.list_changes(changes)
+        },
+
+        ("watch", Some(mtch)) => {
+            debug!("Subcommand: 'watch'");
+            if app.is_present("input_stdin") {
+                return Err(format_err!("Cannot watch data from stdin"))
+            }
+
+            let once = mtch.is_present("once");
+            let interval = match mtch.value_of("interval").or_else(|| config.watch().interval().map(String::as_str)) {
+                Some(interval) => Some(config::parse_duration(interval)?),
+                None if once   => None,
+                None           => return Err(format_err!("No interval passed and none in configuration")),
+            };
+
+            let command = mtch.value_of("command")
+                .map(String::from)
+                .or_else(|| config.watch().command().cloned());
+
+            let projects = config.watch().projects().unwrap_or_else(|| config.watchlist()).clone();
+            let scopes = config.watch().problems().clone();
+            if projects.is_empty() && scopes.is_empty() {
+                return Err(format_err!("Nothing to watch, configure projects or problems in the [watch] section"))
+            }
+            scopes.iter().try_for_each(|scope| watch::scope_key(scope).map(|_| ()))?;
+
+            if let (Some(ttl), Some(interval)) = (config.cache().ttl()?, interval) {
+                if ttl > interval {
+                    warn!("Cache TTL is longer than the interval, changes are only noticed when cached responses expire");
+                }
+            }
+
+            let store = SnapshotStore::open_default()?;
+
+            loop {
+                match watch::poll(&backend, &store, &projects, &scopes) {
+                    Ok((changes, pending)) => {
+                        let reported = if changes.is_empty() {
+                            info!("No changes");
+                            true
+                        } else {
+                            frontend.list_changes(changes.clone())?;
+                            command
+                                .as_ref()
+                                .map(|command| watch::run_hook(command, &changes))
+                                .unwrap_or(Ok(()))
+                                .map(|_| true)
+                                .unwrap_or_else(|e| {
+                                    // Keep the old state, so the changes are reported again
+                                    warn!("{}, changes will be reported again in the next poll", e);
+                                    false
+                                })
+                        };
+
+                        if reported {
+                            watch::commit(&store, pending)?;
+                        }
+                    },
+
+                    Err(e) => warn!("Poll failed: {}", e),
+                }
+
+                match interval {
+                    Some(interval) if !once => {
+                        debug!("Next poll in {}", humantime::format_duration(interval));
+                        std::thread::sleep(interval);
+                    },
+                    _ => break Ok(()),
+                }
+            }
+        },
+
+        ("schema", Some(_)) => {
+            debug!("Subcommand: 'schema'");
+            let schema = serde_json::to_string_pretty(&output::schema())?;
+            writeln!(std::io::stdout(), "{}", schema).map_err(Error::from)
+        },
+
         ("compare", Some(mtch)) => {
             debug!("Subcommand: 'compare'");
             let repos = mtch.values_of("compare-distros").unwrap().map(String::from).map(Repo::new).collect();
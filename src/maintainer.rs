@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use librepology::v1::project::Project;
+use librepology::v1::types::Name;
+use librepology::v1::types::Package;
+use librepology::v1::types::Problem;
+use librepology::v1::types::Repo;
+use librepology::v1::types::Status;
+use librepology::v1::types::Version;
+
+/// A package maintained by a maintainer
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct MaintainedPackage {
+    project: String,
+    repo: Repo,
+    name: Option<Name>,
+    version: Version,
+    status: Option<Status>,
+    newest_version: Option<Version>,
+}
+
+impl MaintainedPackage {
+    pub fn project(&self) -> &String {
+        &self.project
+    }
+
+    pub fn repo(&self) -> &Repo {
+        &self.repo
+    }
+
+    pub fn name(&self) -> Option<&Name> {
+        self.name.as_ref()
+    }
+
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    pub fn status(&self) -> Option<&Status> {
+        self.status.as_ref()
+    }
+
+    pub fn newest_version(&self) -> Option<&Version> {
+        self.newest_version.as_ref()
+    }
+}
+
+/// Everything a maintainer maintains, and the problems with it
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct MaintainerOverview {
+    maintainer: String,
+    packages: Vec<MaintainedPackage>,
+    packages_by_status: BTreeMap<Status, usize>,
+    problems: Vec<Problem>,
+}
+
+impl MaintainerOverview {
+    /// Build the overview from the projects a maintainer maintains and their problems
+    ///
+    /// Only the packages of the projects which are actually maintained by `maintainer` are listed,
+    /// and only those for which `filter` returns true.
+    pub fn new<F>(maintainer: String, projects: Vec<Project>, problems: Vec<Problem>, filter: F) -> Self
+        where F: Fn(&Package) -> bool
+    {
+        let packages = projects
+            .iter()
+            .flat_map(|project| {
+                project.packages()
+                    .iter()
+                    .filter(|p| is_maintained_by(p, &maintainer))
+                    .filter(|p| filter(p))
+                    .map(move |p| MaintainedPackage {
+                        project: project.name().clone(),
+                        repo: p.repo().clone(),
+                        name: p.any_name().cloned(),
+                        version: p.version().clone(),
+                        status: p.status().cloned(),
+                        newest_version: project.newest_version().cloned(),
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        let packages_by_status = packages
+            .iter()
+            .filter_map(MaintainedPackage::status)
+            .fold(BTreeMap::new(), |mut map, status| {
+                *map.entry(status.clone()).or_insert(0) += 1;
+                map
+            });
+
+        MaintainerOverview { maintainer, packages, packages_by_status, problems }
+    }
+
+    pub fn maintainer(&self) -> &String {
+        &self.maintainer
+    }
+
+    pub fn packages(&self) -> &Vec<MaintainedPackage> {
+        &self.packages
+    }
+
+    pub fn packages_by_status(&self) -> &BTreeMap<Status, usize> {
+        &self.packages_by_status
+    }
+
+    pub fn problems(&self) -> &Vec<Problem> {
+        &self.problems
+    }
+}
+
+/// Whether `maintainer` is one of the maintainers of the package
+///
+/// Maintainers are email addresses, so they are compared case-insensitively.
+pub fn is_maintained_by(package: &Package, maintainer: &str) -> bool {
+    package.maintainers()
+        .map(|ms| ms.iter().any(|m| m.eq_ignore_ascii_case(maintainer)))
+        .unwrap_or(false)
+}
@@ -0,0 +1,52 @@
+use librepology::v1::project::Project;
+use librepology::v1::types::Repo;
+use librepology::v1::types::Status;
+use librepology::v1::types::Version;
+
+/// A project which is packaged in some reference repositories but missing in a repository
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct MissingProject {
+    project: String,
+    newest_version: Option<Version>,
+    reference_repos: Vec<Repo>,
+}
+
+impl MissingProject {
+    /// Build the entry for a project, listing the reference repositories which package it
+    ///
+    /// If `newest_only` is set, only reference repositories which carry the newest version are
+    /// listed.
+    pub fn from_project(project: &Project, references: &[Repo], newest_only: bool) -> Self {
+        let mut reference_repos = project.packages()
+            .iter()
+            .filter(|p| references.contains(p.repo()))
+            .filter(|p| !newest_only || matches!(p.status(), Some(Status::Newest) | Some(Status::Unique)))
+            .map(|p| p.repo().clone())
+            .collect::<Vec<_>>();
+        reference_repos.sort();
+        reference_repos.dedup();
+
+        MissingProject {
+            project: project.name().clone(),
+            newest_version: project.newest_or_unique_version().cloned(),
+            reference_repos,
+        }
+    }
+
+    pub fn project(&self) -> &String {
+        &self.project
+    }
+
+    pub fn newest_version(&self) -> Option<&Version> {
+        self.newest_version.as_ref()
+    }
+
+    pub fn reference_repos(&self) -> &Vec<Repo> {
+        &self.reference_repos
+    }
+}
+
+/// Sort missing projects, projects which are packaged in more of the reference repositories first
+pub fn sort_by_references(projects: &mut [MissingProject]) {
+    projects.sort_by_key(|m| std::cmp::Reverse(m.reference_repos().len()));
+}
@@ -0,0 +1,70 @@
+use librepology::v1::project::Project;
+use librepology::v1::types::Name;
+use librepology::v1::types::Repo;
+use librepology::v1::types::Status;
+use librepology::v1::types::Version;
+
+use crate::maintainer::is_maintained_by;
+
+/// A package in a repository which is behind the newest version of its project
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct OutdatedPackage {
+    project: String,
+    name: Option<Name>,
+    version: Version,
+    newest_version: Option<Version>,
+    newest_repos: Vec<Repo>,
+}
+
+impl OutdatedPackage {
+    /// Find the outdated packages of a project in a repository, optionally only the packages of one
+    /// maintainer
+    pub fn from_project(project: &Project, repo: &Repo, maintainer: Option<&str>) -> Vec<OutdatedPackage> {
+        let mut newest_repos = project.packages()
+            .iter()
+            .filter(|p| p.status() == Some(&Status::Newest))
+            .map(|p| p.repo().clone())
+            .collect::<Vec<_>>();
+        newest_repos.sort();
+        newest_repos.dedup();
+
+        project.packages_in_repo(repo)
+            .into_iter()
+            .filter(|p| p.status() == Some(&Status::Outdated))
+            .filter(|p| maintainer.map(|m| is_maintained_by(p, m)).unwrap_or(true))
+            .map(|p| OutdatedPackage {
+                project: project.name().clone(),
+                name: p.any_name().cloned(),
+                version: p.version().clone(),
+                newest_version: project.newest_version().cloned(),
+                newest_repos: newest_repos.clone(),
+            })
+            .collect()
+    }
+
+    pub fn project(&self) -> &String {
+        &self.project
+    }
+
+    pub fn name(&self) -> Option<&Name> {
+        self.name.as_ref()
+    }
+
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    pub fn newest_version(&self) -> Option<&Version> {
+        self.newest_version.as_ref()
+    }
+
+    pub fn newest_repos(&self) -> &Vec<Repo> {
+        &self.newest_repos
+    }
+
+    /// Restrict the list of repositories with the newest version
+    pub fn filter_newest_repos<F: Fn(&Repo) -> bool>(mut self, f: F) -> Self {
+        self.newest_repos.retain(f);
+        self
+    }
+}
@@ -0,0 +1,454 @@
+//! The JSON output of repolocli
+//!
+//! Every JSON output is an envelope with the kind of data, the schema version, the query which
+//! produced the data and the data itself. `schema()` describes the envelope as JSON Schema.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use clap::ArgMatches;
+use failure::Fallible as Result;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::problemkind::ProblemKind;
+
+/// The version of the JSON output, incremented on incompatible changes
+pub const SCHEMA_VERSION : u32 = 1;
+
+/// What kind of data an output contains
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Kind {
+    #[serde(rename = "packages")]
+    Packages,
+
+    #[serde(rename = "projects")]
+    Projects,
+
+    #[serde(rename = "project-summary")]
+    ProjectSummary,
+
+    #[serde(rename = "search-results")]
+    SearchResults,
+
+    #[serde(rename = "outdated")]
+    Outdated,
+
+    #[serde(rename = "missing")]
+    Missing,
+
+    #[serde(rename = "repo-diff")]
+    RepoDiff,
+
+    #[serde(rename = "maintainer-overview")]
+    MaintainerOverview,
+
+    #[serde(rename = "history")]
+    History,
+
+    #[serde(rename = "changes")]
+    Changes,
+
+    #[serde(rename = "problem-groups")]
+    ProblemGroups,
+
+    #[serde(rename = "problem-summary")]
+    ProblemSummary,
+
+    #[serde(rename = "problems")]
+    Problems,
+
+    #[serde(rename = "compare")]
+    Compare,
+}
+
+impl Kind {
+    pub const NAMES: &'static [&'static str] = &[
+        "packages",
+        "projects",
+        "project-summary",
+        "search-results",
+        "outdated",
+        "missing",
+        "repo-diff",
+        "maintainer-overview",
+        "history",
+        "changes",
+        "problem-groups",
+        "problem-summary",
+        "problems",
+        "compare",
+    ];
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Kind::Packages           => write!(f, "packages"),
+            Kind::Projects           => write!(f, "projects"),
+            Kind::ProjectSummary     => write!(f, "project-summary"),
+            Kind::SearchResults      => write!(f, "search-results"),
+            Kind::Outdated           => write!(f, "outdated"),
+            Kind::Missing            => write!(f, "missing"),
+            Kind::RepoDiff           => write!(f, "repo-diff"),
+            Kind::MaintainerOverview => write!(f, "maintainer-overview"),
+            Kind::History            => write!(f, "history"),
+            Kind::Changes            => write!(f, "changes"),
+            Kind::ProblemGroups      => write!(f, "problem-groups"),
+            Kind::ProblemSummary     => write!(f, "problem-summary"),
+            Kind::Problems           => write!(f, "problems"),
+            Kind::Compare            => write!(f, "compare"),
+        }
+    }
+}
+
+/// The invocation of repolocli which produced an output
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct Query {
+    subcommand: Option<String>,
+
+    /// Whether the data was read from stdin instead of repology
+    stdin: bool,
+
+    /// The arguments of the subcommand: `true` for flags, a list of values otherwise
+    parameters: BTreeMap<String, Value>,
+}
+
+/// The arguments recorded in the query of each subcommand
+///
+/// Must list every argument of the subcommand in `cli.rs`, arguments which are not listed are not
+/// recorded.
+fn parameter_names(subcommand: &str) -> &'static [&'static str] {
+    match subcommand {
+        "project"    => &["project_name", "from-file", "sort", "sort-version", "sort-repo", "columns",
+                          "latest", "latest-by", "semver", "include-devel", "summary"],
+        "problems"   => &["repo", "maintainer", "sort", "sort-maintainer", "sort-repo", "columns",
+                          "group-by", "summary", "show-acked"],
+        "search"     => &["term", "repo"],
+        "outdated"   => &["repo", "maintainer"],
+        "missing"    => &["repo", "reference", "newest"],
+        "repo-diff"  => &["repo_a", "repo_b"],
+        "maintainer" => &["maintainer"],
+        "snapshot"   => &["project_name"],
+        "history"    => &["project_name"],
+        "diff"       => &["old", "new"],
+        "watch"      => &["interval", "command", "once"],
+        "compare"    => &["compare-list", "compare-distros", "columns"],
+        _            => &[],
+    }
+}
+
+impl Query {
+    pub fn from_matches(app: &ArgMatches) -> Self {
+        let (subcommand, parameters) = match app.subcommand() {
+            (name, Some(mtch)) => {
+                let parameters = parameter_names(name)
+                    .iter()
+                    .filter(|arg| mtch.is_present(arg))
+                    .map(|arg| {
+                        // Flags have no values
+                        let values = mtch.values_of(arg)
+                            .map(|values| values.map(|v| Value::String(String::from(v))).collect::<Vec<_>>())
+                            .unwrap_or_default();
+
+                        let value = if values.is_empty() { Value::Bool(true) } else { Value::Array(values) };
+                        (arg.to_string(), value)
+                    })
+                    .collect();
+
+                (Some(String::from(name)), parameters)
+            },
+            _ => (None, BTreeMap::new()),
+        };
+
+        Query { subcommand, stdin: app.is_present("input_stdin"), parameters }
+    }
+}
+
+/// A JSON output of repolocli
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Envelope<T> {
+    kind: Kind,
+    schema_version: u32,
+    query: Query,
+    data: T,
+}
+
+impl<T> Envelope<T> {
+    pub fn new(kind: Kind, query: Query, data: T) -> Self {
+        Envelope { kind, schema_version: SCHEMA_VERSION, query, data }
+    }
+
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    pub fn schema_version(&self) -> u32 {
+        self.schema_version
+    }
+
+    pub fn into_data(self) -> T {
+        self.data
+    }
+}
+
+/// Split JSON data into its kind and the data itself, if it is an envelope
+///
+/// Data which is not in an envelope (like responses of the repology API or output of earlier
+/// versions of repolocli) is returned as is, without kind.
+pub fn open(value: Value) -> Result<(Option<Kind>, Value)> {
+    let is_envelope = value.get("schema_version").is_some() && value.get("data").is_some();
+    if !is_envelope {
+        return Ok((None, value))
+    }
+
+    let envelope : Envelope<Value> = serde_json::from_value(value)?;
+    if envelope.schema_version() > SCHEMA_VERSION {
+        return Err(format_err!("Output has schema version {}, but only versions up to {} are supported",
+                               envelope.schema_version(), SCHEMA_VERSION))
+    }
+
+    Ok((Some(envelope.kind()), envelope.into_data()))
+}
+
+/// JSON Schema of the envelope and the data of every kind
+pub fn schema() -> Value {
+    let conditions = Kind::NAMES
+        .iter()
+        .map(|kind| json!({
+            "if": { "properties": { "kind": { "const": kind } } },
+            "then": { "properties": { "data": { "$ref": format!("#/definitions/{}", kind) } } }
+        }))
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "repolocli JSON output",
+        "type": "object",
+        "required": ["kind", "schema_version", "query", "data"],
+        "properties": {
+            "kind": { "enum": Kind::NAMES },
+            "schema_version": { "const": SCHEMA_VERSION },
+            "query": {
+                "type": "object",
+                "required": ["subcommand", "stdin", "parameters"],
+                "properties": {
+                    "subcommand": nullable("string"),
+                    "stdin": { "type": "boolean" },
+                    "parameters": {
+                        "type": "object",
+                        "additionalProperties": {
+                            "oneOf": [{ "const": true }, strings()]
+                        }
+                    }
+                }
+            },
+            "data": {}
+        },
+        "allOf": conditions,
+        "definitions": definitions(),
+    })
+}
+
+fn nullable(ty: &str) -> Value {
+    json!({ "type": [ty, "null"] })
+}
+
+fn strings() -> Value {
+    json!({ "type": "array", "items": { "type": "string" } })
+}
+
+fn counts() -> Value {
+    json!({ "type": "object", "additionalProperties": { "type": "integer", "minimum": 0 } })
+}
+
+fn list_of(item: &str) -> Value {
+    json!({ "type": "array", "items": { "$ref": format!("#/definitions/{}", item) } })
+}
+
+/// An object where all listed properties are required
+fn object(properties: Value) -> Value {
+    let required = properties
+        .as_object()
+        .map(|p| p.keys().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    json!({ "type": "object", "required": required, "properties": properties })
+}
+
+fn package_status() -> Value {
+    json!({
+        "enum": [
+            "newest", "devel", "unique", "outdated", "legacy", "rolling", "noscheme", "incorrect",
+            "untrusted", "ignored", null
+        ]
+    })
+}
+
+fn definitions() -> Value {
+    let package = object(json!({
+        "repo": { "type": "string" },
+        "name": nullable("string"),
+        "srcname": nullable("string"),
+        "binname": nullable("string"),
+        "visiblename": nullable("string"),
+        "version": { "type": "string" },
+        "status": package_status(),
+        "summary": nullable("string"),
+        "licenses": { "type": ["array", "null"], "items": { "type": "string" } },
+        "maintainers": { "type": ["array", "null"], "items": { "type": "string" } },
+        "www": { "type": ["array", "null"], "items": { "type": "string" } },
+        "downloads": { "type": ["array", "null"], "items": { "type": "string" } },
+    }));
+
+    let problem = object(json!({
+        "repo": { "type": "string" },
+        "name": { "type": "string" },
+        "effname": { "type": "string" },
+        "maintainer": { "type": "string" },
+        "problem": { "type": "string" },
+    }));
+
+    let project_summary = object(json!({
+        "newest_version": nullable("string"),
+        "newest_devel_version": nullable("string"),
+        "repos": { "type": "integer" },
+        "repos_by_status": counts(),
+        "maintainers": strings(),
+        "licenses": { "type": ["array", "null"], "items": { "type": "string" } },
+    }));
+
+    let search_result = object(json!({
+        "name": { "type": "string" },
+        "newest_version": nullable("string"),
+        "repos": { "type": "integer" },
+    }));
+
+    let outdated = object(json!({
+        "project": { "type": "string" },
+        "name": nullable("string"),
+        "version": { "type": "string" },
+        "newest_version": nullable("string"),
+        "newest_repos": strings(),
+    }));
+
+    let missing = object(json!({
+        "project": { "type": "string" },
+        "newest_version": nullable("string"),
+        "reference_repos": strings(),
+    }));
+
+    let repo_diff_entry = object(json!({
+        "project": { "type": "string" },
+        "status": { "enum": ["a-newer", "b-newer", "equal", "only-a", "only-b"] },
+        "version_a": nullable("string"),
+        "version_b": nullable("string"),
+    }));
+
+    let maintainer_package = object(json!({
+        "project": { "type": "string" },
+        "repo": { "type": "string" },
+        "name": nullable("string"),
+        "version": { "type": "string" },
+        "status": package_status(),
+        "newest_version": nullable("string"),
+    }));
+
+    let history_entry = object(json!({
+        "timestamp": { "type": "string", "format": "date-time" },
+        "repo": { "type": "string" },
+        "name": nullable("string"),
+        "old_version": nullable("string"),
+        "new_version": nullable("string"),
+    }));
+
+    let package_change = object(json!({
+        "project": nullable("string"),
+        "repo": { "type": "string" },
+        "name": nullable("string"),
+        "change": { "enum": ["appeared", "disappeared", "changed"] },
+        "old_version": nullable("string"),
+        "new_version": nullable("string"),
+        "old_status": package_status(),
+        "new_status": package_status(),
+    }));
+
+    let problem_change = json!({
+        "allOf": [
+            { "$ref": "#/definitions/problem" },
+            object(json!({ "change": { "enum": ["new", "fixed"] } })),
+        ]
+    });
+
+    let problem_group = object(json!({
+        "key": { "type": "string" },
+        "count": { "type": "integer" },
+        "count_by_kind": counts(),
+        "problems": list_of("problem"),
+    }));
+
+    let compare_result = object(json!({
+        "package_name": { "type": "string" },
+        "local_version": { "type": "string" },
+        "comparisons": {
+            "type": "array",
+            "items": object(json!({
+                "version": { "type": "string" },
+                "repo": { "type": "string" },
+            })),
+        },
+    }));
+
+    json!({
+        "package": package,
+        "problem": problem,
+        "problem_kind": { "enum": ProblemKind::NAMES },
+        "packages": list_of("package"),
+        "projects": {
+            "type": "object",
+            "additionalProperties": {
+                "oneOf": [list_of("package"), { "type": "null" }]
+            }
+        },
+        "project-summary": {
+            "type": "object",
+            "additionalProperties": {
+                "oneOf": [project_summary, { "type": "null" }]
+            }
+        },
+        "search-results": { "type": "array", "items": search_result },
+        "outdated": { "type": "array", "items": outdated },
+        "missing": { "type": "array", "items": missing },
+        "repo-diff": object(json!({
+            "repo_a": { "type": "string" },
+            "repo_b": { "type": "string" },
+            "projects": { "type": "array", "items": repo_diff_entry },
+        })),
+        "maintainer-overview": object(json!({
+            "maintainer": { "type": "string" },
+            "packages": { "type": "array", "items": maintainer_package },
+            "packages_by_status": counts(),
+            "problems": list_of("problem"),
+        })),
+        "history": { "type": "array", "items": history_entry },
+        "changes": object(json!({
+            "packages": { "type": "array", "items": package_change },
+            "problems": { "type": "array", "items": problem_change },
+        })),
+        "problem-groups": object(json!({
+            "group_by": { "enum": ["repo", "maintainer", "kind", "project"] },
+            "groups": { "type": "array", "items": problem_group },
+        })),
+        "problem-summary": object(json!({
+            "total": { "type": "integer" },
+            "by_kind": {
+                "type": "object",
+                "propertyNames": { "$ref": "#/definitions/problem_kind" },
+                "additionalProperties": { "type": "integer", "minimum": 0 }
+            },
+            "by_maintainer": counts(),
+        })),
+        "problems": list_of("problem"),
+        "compare": { "type": "array", "items": compare_result },
+    })
+}
@@ -0,0 +1,135 @@
+use std::fmt;
+use std::str::FromStr;
+
+use failure::Error;
+use failure::Fallible as Result;
+
+use librepology::v1::types::Problem;
+
+/// The kind of a problem
+///
+/// Repology does not report the kind of a problem, it is guessed from the problem description.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum ProblemKind {
+    #[serde(rename = "homepage-dead")]
+    HomepageDead,
+
+    #[serde(rename = "homepage-redirect")]
+    HomepageRedirect,
+
+    #[serde(rename = "homepage-discontinued")]
+    HomepageDiscontinued,
+
+    #[serde(rename = "download-dead")]
+    DownloadDead,
+
+    #[serde(rename = "download-redirect")]
+    DownloadRedirect,
+
+    #[serde(rename = "cpe")]
+    Cpe,
+
+    #[serde(rename = "other")]
+    Other,
+}
+
+impl ProblemKind {
+    /// Names of all kinds, as accepted by `FromStr`
+    pub const NAMES: &'static [&'static str] = &[
+        "homepage-dead",
+        "homepage-redirect",
+        "homepage-discontinued",
+        "download-dead",
+        "download-redirect",
+        "cpe",
+        "other",
+    ];
+
+    /// Guess the kind of a problem from its description
+    pub fn of(problem: &Problem) -> Self {
+        let desc = problem.problem_description().to_lowercase();
+        let is_homepage = desc.starts_with("homepage link");
+        let is_download = desc.starts_with("download link");
+
+        if is_homepage && desc.contains("is dead") {
+            ProblemKind::HomepageDead
+        } else if is_homepage && desc.contains("redirect") {
+            ProblemKind::HomepageRedirect
+        } else if is_homepage && (desc.contains("discontinued") || desc.contains("no longer")) {
+            ProblemKind::HomepageDiscontinued
+        } else if is_download && desc.contains("is dead") {
+            ProblemKind::DownloadDead
+        } else if is_download && desc.contains("redirect") {
+            ProblemKind::DownloadRedirect
+        } else if desc.contains("cpe information") {
+            // "CPE information is missing for this package ...", "CPE information defined for
+            // this package was not found ..."
+            ProblemKind::Cpe
+        } else {
+            ProblemKind::Other
+        }
+    }
+}
+
+impl fmt::Display for ProblemKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProblemKind::HomepageDead         => write!(f, "homepage-dead"),
+            ProblemKind::HomepageRedirect     => write!(f, "homepage-redirect"),
+            ProblemKind::HomepageDiscontinued => write!(f, "homepage-discontinued"),
+            ProblemKind::DownloadDead         => write!(f, "download-dead"),
+            ProblemKind::DownloadRedirect     => write!(f, "download-redirect"),
+            ProblemKind::Cpe                  => write!(f, "cpe"),
+            ProblemKind::Other                => write!(f, "other"),
+        }
+    }
+}
+
+impl FromStr for ProblemKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "homepage-dead"         => Ok(ProblemKind::HomepageDead),
+            "homepage-redirect"     => Ok(ProblemKind::HomepageRedirect),
+            "homepage-discontinued" => Ok(ProblemKind::HomepageDiscontinued),
+            "download-dead"         => Ok(ProblemKind::DownloadDead),
+            "download-redirect"     => Ok(ProblemKind::DownloadRedirect),
+            "cpe"                   => Ok(ProblemKind::Cpe),
+            "other"                 => Ok(ProblemKind::Other),
+            other                   => Err(format_err!("Unknown problem kind: {}", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind_of(description: &str) -> ProblemKind {
+        let json = format!(r#"{{"repo": "ours", "name": "foo", "effname": "foo", "maintainer": "me@x", "problem": {}}}"#,
+                           serde_json::to_string(description).unwrap());
+        let problem: Problem = serde_json::from_str(&json).unwrap();
+        ProblemKind::of(&problem)
+    }
+
+    #[test]
+    fn test_homepage_and_download() {
+        assert_eq!(kind_of("Homepage link \"http://foo.org\" is dead (HTTP error 404) for more than a month."), ProblemKind::HomepageDead);
+        assert_eq!(kind_of("Homepage link \"http://foo.org\" is a permanent redirect to \"https://foo.org\" and should be updated"), ProblemKind::HomepageRedirect);
+        assert_eq!(kind_of("Download link \"http://foo.org/foo.tar.gz\" is dead (HTTP error 404) for more than a month."), ProblemKind::DownloadDead);
+        assert_eq!(kind_of("Download link \"http://foo.org/foo.tar.gz\" is a permanent redirect to \"https://foo.org/foo.tar.gz\" and should be updated"), ProblemKind::DownloadRedirect);
+    }
+
+    #[test]
+    fn test_cpe_in_url_is_not_cpe() {
+        assert_eq!(kind_of("Homepage link \"http://cpe.example.org\" is dead (HTTP error 404) for more than a month."), ProblemKind::HomepageDead);
+        assert_eq!(kind_of("Something about http://libcpe.org"), ProblemKind::Other);
+    }
+
+    #[test]
+    fn test_cpe() {
+        assert_eq!(kind_of("CPE information is missing for this package, while repository has it for other packages"), ProblemKind::Cpe);
+        assert_eq!(kind_of("CPE information defined for this package was not found in the NVD database"), ProblemKind::Cpe);
+    }
+}
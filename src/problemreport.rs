@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use failure::Error;
+use failure::Fallible as Result;
+
+use librepology::v1::types::Problem;
+
+use crate::problemkind::ProblemKind;
+
+/// What to group problems by
+#[derive(Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum GroupBy {
+    #[serde(rename = "repo")]
+    Repo,
+
+    #[serde(rename = "maintainer")]
+    Maintainer,
+
+    #[serde(rename = "kind")]
+    Kind,
+
+    #[serde(rename = "project")]
+    Project,
+}
+
+impl GroupBy {
+    /// The key of the group a problem belongs to
+    fn key(self, problem: &Problem) -> String {
+        match self {
+            GroupBy::Repo       => problem.repo().deref().clone(),
+            GroupBy::Maintainer => problem.maintainer().deref().clone(),
+            GroupBy::Kind       => ProblemKind::of(problem).to_string(),
+            GroupBy::Project    => problem.effname().deref().clone(),
+        }
+    }
+}
+
+impl fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GroupBy::Repo       => write!(f, "repo"),
+            GroupBy::Maintainer => write!(f, "maintainer"),
+            GroupBy::Kind       => write!(f, "kind"),
+            GroupBy::Project    => write!(f, "project"),
+        }
+    }
+}
+
+impl FromStr for GroupBy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "repo"       => Ok(GroupBy::Repo),
+            "maintainer" => Ok(GroupBy::Maintainer),
+            "kind"       => Ok(GroupBy::Kind),
+            "project"    => Ok(GroupBy::Project),
+            other        => Err(format_err!("Cannot group problems by: {}", other)),
+        }
+    }
+}
+
+fn count_by_kind(problems: &[Problem]) -> BTreeMap<ProblemKind, usize> {
+    problems.iter().fold(BTreeMap::new(), |mut map, problem| {
+        *map.entry(ProblemKind::of(problem)).or_insert(0) += 1;
+        map
+    })
+}
+
+/// Problems which have the same repository, maintainer, kind or project
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct ProblemGroup {
+    key: String,
+    count: usize,
+    count_by_kind: BTreeMap<ProblemKind, usize>,
+    problems: Vec<Problem>,
+}
+
+impl ProblemGroup {
+    pub fn key(&self) -> &String {
+        &self.key
+    }
+
+    /// The number of problems as text, "1 problem" or "2 problems"
+    pub fn count_text(&self) -> String {
+        match self.count {
+            1     => String::from("1 problem"),
+            count => format!("{} problems", count),
+        }
+    }
+
+    pub fn count_by_kind(&self) -> &BTreeMap<ProblemKind, usize> {
+        &self.count_by_kind
+    }
+
+    pub fn problems(&self) -> &Vec<Problem> {
+        &self.problems
+    }
+
+    pub fn into_problems(self) -> Vec<Problem> {
+        self.problems
+    }
+}
+
+/// Group problems
+///
+/// The groups are sorted by key, the problems keep their order within a group.
+pub fn group(problems: Vec<Problem>, by: GroupBy) -> Vec<ProblemGroup> {
+    problems
+        .into_iter()
+        .fold(BTreeMap::new(), |mut map: BTreeMap<String, Vec<Problem>>, problem| {
+            map.entry(by.key(&problem)).or_default().push(problem);
+            map
+        })
+        .into_iter()
+        .map(|(key, problems)| ProblemGroup {
+            key,
+            count: problems.len(),
+            count_by_kind: count_by_kind(&problems),
+            problems,
+        })
+        .collect()
+}
+
+/// Totals of problems per kind and per maintainer
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct ProblemSummary {
+    total: usize,
+    by_kind: BTreeMap<ProblemKind, usize>,
+    by_maintainer: BTreeMap<String, usize>,
+}
+
+impl ProblemSummary {
+    pub fn new(problems: &[Problem]) -> Self {
+        let by_maintainer = problems.iter().fold(BTreeMap::new(), |mut map, problem| {
+            *map.entry(problem.maintainer().deref().clone()).or_insert(0) += 1;
+            map
+        });
+
+        ProblemSummary {
+            total: problems.len(),
+            by_kind: count_by_kind(problems),
+            by_maintainer,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    pub fn by_kind(&self) -> &BTreeMap<ProblemKind, usize> {
+        &self.by_kind
+    }
+
+    pub fn by_maintainer(&self) -> &BTreeMap<String, usize> {
+        &self.by_maintainer
+    }
+}
@@ -0,0 +1,111 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use librepology::v1::project::Project;
+use librepology::v1::types::Repo;
+use librepology::v1::types::Version;
+use librepology::v1::vercmp;
+
+/// How a project compares between two repositories
+#[derive(Serialize, Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum DiffStatus {
+    #[serde(rename = "a-newer")]
+    ANewer,
+
+    #[serde(rename = "b-newer")]
+    BNewer,
+
+    #[serde(rename = "equal")]
+    Equal,
+
+    #[serde(rename = "only-a")]
+    OnlyA,
+
+    #[serde(rename = "only-b")]
+    OnlyB,
+}
+
+impl fmt::Display for DiffStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiffStatus::ANewer => write!(f, "a-newer"),
+            DiffStatus::BNewer => write!(f, "b-newer"),
+            DiffStatus::Equal  => write!(f, "equal"),
+            DiffStatus::OnlyA  => write!(f, "only-a"),
+            DiffStatus::OnlyB  => write!(f, "only-b"),
+        }
+    }
+}
+
+/// One project in the diff of two repositories
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct RepoDiffEntry {
+    project: String,
+    status: DiffStatus,
+    version_a: Option<Version>,
+    version_b: Option<Version>,
+}
+
+impl RepoDiffEntry {
+    pub fn project(&self) -> &String {
+        &self.project
+    }
+
+    pub fn status(&self) -> DiffStatus {
+        self.status
+    }
+
+    pub fn version_a(&self) -> Option<&Version> {
+        self.version_a.as_ref()
+    }
+
+    pub fn version_b(&self) -> Option<&Version> {
+        self.version_b.as_ref()
+    }
+}
+
+/// The highest version of a project in a repository
+///
+/// A repository may have several packages for a project, we compare the most recent one.
+fn version_in(project: &Project, repo: &Repo) -> Option<Version> {
+    project.packages_in_repo(repo)
+        .into_iter()
+        .map(|p| p.version())
+        .max_by(|a, b| vercmp::compare(a, b))
+        .cloned()
+}
+
+/// Diff two repositories, given the projects present in repository A and in repository B
+///
+/// The result is sorted by status, then by project name.
+pub fn diff(repo_a: &Repo, repo_b: &Repo, projects_a: Vec<Project>, projects_b: Vec<Project>) -> Vec<RepoDiffEntry> {
+    let projects = projects_a
+        .into_iter()
+        .chain(projects_b)
+        .map(|p| (p.name().clone(), p))
+        .collect::<BTreeMap<String, Project>>();
+
+    let mut entries = projects
+        .iter()
+        .map(|(name, project)| {
+            let version_a = version_in(project, repo_a);
+            let version_b = version_in(project, repo_b);
+
+            let status = match (version_a.as_ref(), version_b.as_ref()) {
+                (Some(a), Some(b)) => match vercmp::compare(a, b) {
+                    Ordering::Greater => DiffStatus::ANewer,
+                    Ordering::Less    => DiffStatus::BNewer,
+                    Ordering::Equal   => DiffStatus::Equal,
+                },
+                (Some(_), None) => DiffStatus::OnlyA,
+                _               => DiffStatus::OnlyB,
+            };
+
+            RepoDiffEntry { project: name.clone(), status, version_a, version_b }
+        })
+        .collect::<Vec<_>>();
+
+    entries.sort_by(|a, b| a.status.cmp(&b.status).then_with(|| a.project.cmp(&b.project)));
+    entries
+}
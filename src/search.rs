@@ -0,0 +1,56 @@
+use failure::Fallible as Result;
+
+use librepology::v1::api::Api;
+use librepology::v1::filter::ProjectFilter;
+use librepology::v1::project::Project;
+
+use crate::backend::Backend;
+
+/// Maximum number of suggestions for a project name
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Search for projects with `term` in their name
+///
+/// All pages of results are fetched. If `repos` is not empty, only projects present in at least
+/// one of these repositories are returned.
+pub fn search(backend: &Backend, term: &str, repos: &[String]) -> Result<Vec<Project>> {
+    let filter = ProjectFilter::new().search(term);
+
+    if repos.is_empty() {
+        return backend.all_projects(&filter)
+    }
+
+    let mut projects = vec![];
+    for repo in repos {
+        trace!("Searching for '{}' in '{}'", term, repo);
+        projects.append(&mut backend.all_projects(&filter.clone().inrepo(repo.as_str()))?);
+    }
+
+    projects.sort_by(|a, b| a.name().cmp(b.name()));
+    projects.dedup_by(|a, b| a.name() == b.name());
+    Ok(projects)
+}
+
+/// Find names of existing projects which are similar to `name`
+///
+/// The suggestions are sorted by similarity, the most similar name first. Only the first page of
+/// search results is used, to not send a lot of requests for a suggestion.
+pub fn suggestions(backend: &Backend, name: &str) -> Result<Vec<String>> {
+    let first_page = |term: &str| backend.projects(None, &ProjectFilter::new().search(term));
+    let mut candidates = first_page(name)?;
+
+    // A typo in the name does not yield search results, so retry with a prefix of the name
+    if candidates.is_empty() && name.chars().count() > 3 {
+        let prefix = name.chars().take(3).collect::<String>();
+        trace!("No suggestions for '{}', retrying with '{}'", name, prefix);
+        candidates = first_page(&prefix)?;
+    }
+
+    let mut names = candidates
+        .iter()
+        .map(|project| (strsim::levenshtein(name, project.name()), project.name().clone()))
+        .collect::<Vec<_>>();
+    names.sort();
+
+    Ok(names.into_iter().take(MAX_SUGGESTIONS).map(|(_, name)| name).collect())
+}
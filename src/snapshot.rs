@@ -0,0 +1,142 @@
+use std::fs::OpenOptions;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::Utc;
+use failure::Error;
+use failure::Fallible as Result;
+use failure::ResultExt;
+
+use librepology::v1::types::Package;
+use librepology::v1::types::Problem;
+
+/// The state of a project at a certain point in time
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct Snapshot {
+    timestamp: DateTime<Utc>,
+    project: String,
+    packages: Vec<Package>,
+}
+
+impl Snapshot {
+    pub fn new(timestamp: DateTime<Utc>, project: String, packages: Vec<Package>) -> Self {
+        Snapshot { timestamp, project, packages }
+    }
+
+    pub fn timestamp(&self) -> &DateTime<Utc> {
+        &self.timestamp
+    }
+
+    pub fn project(&self) -> &String {
+        &self.project
+    }
+
+    pub fn packages(&self) -> &Vec<Package> {
+        &self.packages
+    }
+}
+
+/// Local store for snapshots
+///
+/// The store is a directory with one file per project in the `projects` subdirectory. Each file
+/// contains one snapshot per line, serialized as JSON, oldest first. The `problems` subdirectory
+/// contains the most recent problems of each watched scope. See the README for the format.
+pub struct SnapshotStore {
+    path: PathBuf,
+}
+
+impl SnapshotStore {
+    /// Open the store in `$XDG_DATA_HOME/repolocli/snapshots`, creating it if necessary
+    pub fn open_default() -> Result<Self> {
+        let path = xdg::BaseDirectories::with_prefix("repolocli")?
+            .create_data_directory("snapshots")
+            .context("Creating snapshot directory")?;
+
+        Ok(SnapshotStore { path })
+    }
+
+    fn project_file(&self, project: &str) -> PathBuf {
+        // project names do not contain slashes, but better be safe than sorry
+        self.path.join("projects").join(format!("{}.jsonl", project.replace('/', "_")))
+    }
+
+    fn problems_file(&self, scope: &str) -> PathBuf {
+        self.path.join("problems").join(format!("{}.json", scope.replace('/', "_")))
+    }
+
+    /// Load the most recently stored problems of a scope, if any were stored
+    pub fn load_problems(&self, scope: &str) -> Result<Option<Vec<Problem>>> {
+        let path = self.problems_file(scope);
+        if !path.exists() {
+            trace!("No problems at {}", path.display());
+            return Ok(None)
+        }
+
+        let buffer = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&buffer)
+            .with_context(|_| format!("Parsing problems in {}", path.display()))
+            .map(Some)
+            .map_err(Error::from)
+    }
+
+    /// Store the problems of a scope, replacing the previously stored ones
+    pub fn store_problems(&self, scope: &str, problems: &[Problem]) -> Result<()> {
+        let path = self.problems_file(scope);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        trace!("Writing problems to {}", path.display());
+        std::fs::write(&path, serde_json::to_string(problems)?).map_err(Error::from)
+    }
+
+    /// Load all snapshots of a project, oldest first
+    pub fn load(&self, project: &str) -> Result<Vec<Snapshot>> {
+        let path = self.project_file(project);
+        if !path.exists() {
+            trace!("No snapshots at {}", path.display());
+            return Ok(vec![])
+        }
+
+        let file = std::fs::File::open(&path)?;
+        BufReader::new(file)
+            .lines()
+            .filter(|line| line.as_ref().map(|l| !l.trim().is_empty()).unwrap_or(true))
+            .map(|line| {
+                serde_json::from_str(&line?)
+                    .with_context(|_| format!("Parsing snapshot in {}", path.display()))
+                    .map_err(Error::from)
+            })
+            .collect()
+    }
+
+    /// Load the most recent snapshot of a project
+    pub fn latest(&self, project: &str) -> Result<Option<Snapshot>> {
+        self.load(project).map(|mut snapshots| snapshots.pop())
+    }
+
+    /// Store a snapshot, unless the packages did not change since the last snapshot
+    ///
+    /// Returns whether the snapshot was stored.
+    pub fn record(&self, snapshot: &Snapshot) -> Result<bool> {
+        if let Some(latest) = self.latest(snapshot.project())? {
+            if latest.packages() == snapshot.packages() {
+                trace!("'{}' did not change since {}", snapshot.project(), latest.timestamp());
+                return Ok(false)
+            }
+        }
+
+        let path = self.project_file(snapshot.project());
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        trace!("Appending snapshot to {}", path.display());
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{}", serde_json::to_string(snapshot)?)?;
+        Ok(true)
+    }
+}
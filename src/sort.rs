@@ -0,0 +1,211 @@
+use std::cmp::Ordering;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use failure::Error;
+use failure::Fallible as Result;
+
+use librepology::v1::types::Package;
+use librepology::v1::types::Problem;
+use librepology::v1::vercmp;
+
+/// Something that can be sorted by a `SortSpec`
+pub trait Sortable {
+    /// The keys which can be used for sorting
+    const SORT_KEYS: &'static [&'static str];
+
+    /// Compare two objects by one key
+    ///
+    /// The key is guaranteed to be one of `SORT_KEYS`.
+    fn cmp_by_key(&self, other: &Self, key: &str) -> Ordering;
+}
+
+/// One key to sort by, possibly in descending order
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct SortKey {
+    key: String,
+    descending: bool,
+}
+
+/// A list of keys to sort by
+///
+/// Parsed from a comma-separated list of keys, where each key can be prefixed with a "-" to sort
+/// in descending order, for example `status,-version,repo`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SortSpec(Vec<SortKey>);
+
+impl FromStr for SortSpec {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        s.split(',')
+            .map(str::trim)
+            .map(|key| {
+                let descending = key.starts_with('-');
+                let key = key.trim_start_matches(&['-', '+'][..]);
+
+                if key.is_empty() {
+                    Err(format_err!("Empty sort key in '{}'", s))
+                } else {
+                    Ok(SortKey { key: String::from(key), descending })
+                }
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(SortSpec)
+    }
+}
+
+impl SortSpec {
+    /// Check whether all keys of this spec can be used to sort `T`
+    pub fn validate<T: Sortable>(&self) -> Result<()> {
+        match self.0.iter().find(|k| !T::SORT_KEYS.contains(&k.key.as_str())) {
+            Some(k) => Err(format_err!("Cannot sort by '{}', possible keys: {}", k.key, T::SORT_KEYS.join(", "))),
+            None    => Ok(()),
+        }
+    }
+
+    /// Sort the list, first by the first key, then by the second, ...
+    ///
+    /// The sort is stable, so elements which compare equal by all keys keep their order.
+    pub fn sort<T: Sortable>(&self, list: &mut [T]) {
+        list.sort_by(|a, b| {
+            self.0.iter().fold(Ordering::Equal, |ordering, key| {
+                ordering.then_with(|| {
+                    let o = a.cmp_by_key(b, &key.key);
+                    if key.descending { o.reverse() } else { o }
+                })
+            })
+        })
+    }
+}
+
+/// Compare optional values, sorting missing values last
+fn cmp_opt<T, F>(a: Option<T>, b: Option<T>, cmp: F) -> Ordering
+    where F: FnOnce(T, T) -> Ordering
+{
+    match (a, b) {
+        (Some(a), Some(b)) => cmp(a, b),
+        (Some(_), None)    => Ordering::Less,
+        (None, Some(_))    => Ordering::Greater,
+        (None, None)       => Ordering::Equal,
+    }
+}
+
+/// Compare optional lists of things that can be represented as strings
+fn cmp_opt_list<T, F>(a: Option<&Vec<T>>, b: Option<&Vec<T>>, f: F) -> Ordering
+    where F: Fn(&T) -> &str
+{
+    cmp_opt(a, b, |a, b| a.iter().map(&f).cmp(b.iter().map(&f)))
+}
+
+impl Sortable for Package {
+    const SORT_KEYS: &'static [&'static str] = &[
+        "repo",
+        "name",
+        "srcname",
+        "binname",
+        "visiblename",
+        "version",
+        "status",
+        "summary",
+        "licenses",
+        "maintainers",
+        "www",
+        "downloads",
+    ];
+
+    fn cmp_by_key(&self, other: &Self, key: &str) -> Ordering {
+        match key {
+            "repo"        => self.repo().cmp(other.repo()),
+            "name"        => cmp_opt(self.name(), other.name(), Ord::cmp),
+            "srcname"     => cmp_opt(self.srcname(), other.srcname(), Ord::cmp),
+            "binname"     => cmp_opt(self.binname(), other.binname(), Ord::cmp),
+            "visiblename" => cmp_opt(self.visiblename(), other.visiblename(), Ord::cmp),
+            "version"     => vercmp::compare(self.version(), other.version()),
+            "status"      => cmp_opt(self.status(), other.status(), Ord::cmp),
+            "summary"     => cmp_opt(self.summary(), other.summary(), Ord::cmp),
+            "licenses"    => cmp_opt_list(self.licenses(), other.licenses(), |l| l.deref()),
+            "maintainers" => cmp_opt_list(self.maintainers(), other.maintainers(), |m| m.deref()),
+            "www"         => cmp_opt_list(self.www(), other.www(), |w| w.as_str()),
+            "downloads"   => cmp_opt_list(self.downloads(), other.downloads(), |d| d.as_str()),
+            _             => Ordering::Equal,
+        }
+    }
+}
+
+impl Sortable for Problem {
+    const SORT_KEYS: &'static [&'static str] = &[
+        "repo",
+        "name",
+        "effname",
+        "maintainer",
+        "problem",
+    ];
+
+    fn cmp_by_key(&self, other: &Self, key: &str) -> Ordering {
+        match key {
+            "repo"       => self.repo().cmp(other.repo()),
+            "name"       => self.name().cmp(other.name()),
+            "effname"    => self.effname().cmp(other.effname()),
+            "maintainer" => self.maintainer().cmp(other.maintainer()),
+            "problem"    => self.problem_description().cmp(other.problem_description()),
+            _            => Ordering::Equal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Eq, PartialEq)]
+    struct Entry(&'static str, u32);
+
+    impl Sortable for Entry {
+        const SORT_KEYS: &'static [&'static str] = &["name", "number"];
+
+        fn cmp_by_key(&self, other: &Self, key: &str) -> Ordering {
+            match key {
+                "name"   => self.0.cmp(other.0),
+                "number" => self.1.cmp(&other.1),
+                _        => Ordering::Equal,
+            }
+        }
+    }
+
+    fn key(key: &str, descending: bool) -> SortKey {
+        SortKey { key: String::from(key), descending }
+    }
+
+    #[test]
+    fn test_parse() {
+        let spec = "status,-version, +repo".parse::<SortSpec>().unwrap();
+        assert_eq!(spec, SortSpec(vec![key("status", false), key("version", true), key("repo", false)]));
+    }
+
+    #[test]
+    fn test_parse_empty_key() {
+        assert!("".parse::<SortSpec>().is_err());
+        assert!("repo,".parse::<SortSpec>().is_err());
+        assert!("repo,-".parse::<SortSpec>().is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!("name,-number".parse::<SortSpec>().unwrap().validate::<Entry>().is_ok());
+        assert!("name,version".parse::<SortSpec>().unwrap().validate::<Entry>().is_err());
+        assert!("maintainer".parse::<SortSpec>().unwrap().validate::<Package>().is_err());
+        assert!("maintainer".parse::<SortSpec>().unwrap().validate::<Problem>().is_ok());
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut list = vec![Entry("b", 1), Entry("a", 1), Entry("b", 2), Entry("a", 3)];
+
+        "name,-number".parse::<SortSpec>().unwrap().sort(&mut list);
+        assert_eq!(list, vec![Entry("a", 3), Entry("a", 1), Entry("b", 2), Entry("b", 1)]);
+
+        "number".parse::<SortSpec>().unwrap().sort(&mut list);
+        assert_eq!(list, vec![Entry("a", 1), Entry("b", 1), Entry("b", 2), Entry("a", 3)]);
+    }
+}
@@ -0,0 +1,143 @@
+use std::io::ErrorKind;
+use std::io::Write;
+use std::ops::Deref;
+use std::process::Command;
+use std::process::Stdio;
+
+use chrono::Utc;
+use failure::Error;
+use failure::Fallible as Result;
+
+use librepology::v1::api::Api;
+use librepology::v1::types::Problem;
+
+use crate::backend::Backend;
+use crate::changes;
+use crate::changes::ChangeSet;
+use crate::changes::PackageState;
+use crate::config::ProblemScope;
+use crate::snapshot::Snapshot;
+use crate::snapshot::SnapshotStore;
+
+/// New state, which is stored once the changes were reported
+pub enum Pending {
+    Snapshot(Snapshot),
+    Problems(String, Vec<Problem>),
+}
+
+/// The name under which the problems of a scope are stored
+pub fn scope_key(scope: &ProblemScope) -> Result<String> {
+    match (scope.maintainer(), scope.repo()) {
+        (Some(m), Some(r)) => Ok(format!("maintainer_{}_repo_{}", m, r)),
+        (Some(m), None)    => Ok(format!("maintainer_{}", m)),
+        (None, Some(r))    => Ok(format!("repo_{}", r)),
+        (None, None)       => Err(format_err!("Problem scope without 'repo' and 'maintainer' in configuration")),
+    }
+}
+
+fn fetch_problems(backend: &Backend, scope: &ProblemScope) -> Result<Vec<Problem>> {
+    match (scope.maintainer(), scope.repo()) {
+        (Some(m), repo) => backend.problems_for_maintainer(m).map(|problems| {
+            problems
+                .into_iter()
+                .filter(|p| repo.map(|r| p.repo().deref() == r).unwrap_or(true))
+                .collect()
+        }),
+        (None, Some(r)) => backend.problems_for_repo(r),
+        (None, None)    => Err(format_err!("Problem scope without 'repo' and 'maintainer' in configuration")),
+    }
+}
+
+/// Poll all projects and problem scopes once and compare them with the stored state
+///
+/// Errors while fetching one project or scope are logged and the project or scope is skipped, so
+/// that it is compared again in the next poll. If there is no stored state for a project or scope
+/// yet, the fetched state is stored without reporting changes.
+pub fn poll(backend: &Backend, store: &SnapshotStore, projects: &[String], scopes: &[ProblemScope])
+    -> Result<(ChangeSet, Vec<Pending>)>
+{
+    let mut changeset = ChangeSet::default();
+    let mut pending = vec![];
+    let timestamp = Utc::now();
+
+    for project in projects {
+        debug!("Polling project '{}'", project);
+        let packages = match backend.project(project) {
+            Ok(packages) => packages,
+            Err(e) => {
+                warn!("Could not fetch '{}', skipping: {}", project, e);
+                continue
+            },
+        };
+
+        if let Some(latest) = store.latest(project)? {
+            let states = |packages: &[_]| packages
+                .iter()
+                .map(|p| PackageState::from_package(Some(project.clone()), p))
+                .collect::<Vec<_>>();
+
+            let changes = changes::package_changes(states(latest.packages()), states(&packages));
+            changeset.extend(ChangeSet::new(changes, vec![]));
+        } else {
+            info!("First poll of '{}', nothing to compare with", project);
+        }
+
+        pending.push(Pending::Snapshot(Snapshot::new(timestamp, project.clone(), packages)));
+    }
+
+    for scope in scopes {
+        let key = scope_key(scope)?;
+        debug!("Polling problems '{}'", key);
+        let problems = match fetch_problems(backend, scope) {
+            Ok(problems) => problems,
+            Err(e) => {
+                warn!("Could not fetch problems '{}', skipping: {}", key, e);
+                continue
+            },
+        };
+
+        if let Some(old) = store.load_problems(&key)? {
+            changeset.extend(ChangeSet::new(vec![], changes::problem_changes(&old, &problems)));
+        } else {
+            info!("First poll of problems '{}', nothing to compare with", key);
+        }
+
+        pending.push(Pending::Problems(key, problems));
+    }
+
+    Ok((changeset, pending))
+}
+
+/// Store the state of a poll
+pub fn commit(store: &SnapshotStore, pending: Vec<Pending>) -> Result<()> {
+    pending.into_iter().try_for_each(|p| match p {
+        Pending::Snapshot(snapshot)      => store.record(&snapshot).map(|_| ()),
+        Pending::Problems(key, problems) => store.store_problems(&key, &problems),
+    })
+}
+
+/// Run `command` with `sh -c`, passing the changes as JSON on stdin
+pub fn run_hook(command: &str, changes: &ChangeSet) -> Result<()> {
+    debug!("Running hook: {}", command);
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .spawn()?;
+
+    {
+        let stdin = child.stdin.as_mut().ok_or_else(|| format_err!("Could not open stdin of hook"))?;
+        match stdin.write_all(serde_json::to_string_pretty(changes)?.as_bytes()) {
+            // the command does not have to read the changes
+            Err(ref e) if e.kind() == ErrorKind::BrokenPipe => trace!("Hook did not read stdin"),
+            other => other?,
+        }
+    } // close stdin, so the command sees EOF
+
+    let status = child.wait().map_err(Error::from)?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format_err!("Hook '{}' failed: {}", command, status))
+    }
+}